@@ -0,0 +1,93 @@
+// Turns every example under samples/ into a test of its own; see src/examples.rs for what the
+// example files look like. The tests end up in $OUT_DIR/samples.rs, which the aoc library pulls in.
+// Same for the round by round printouts under rounds/ (see src/rounds.rs), in $OUT_DIR/rounds.rs.
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    println!("cargo:rerun-if-changed=samples");
+    println!("cargo:rerun-if-changed=rounds");
+    let root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).to_path_buf();
+    let samples = root.join("samples");
+    let mut tests = String::new();
+    for (day, name, path) in day_files(&samples) {
+        let text = fs::read_to_string(&path).unwrap();
+        // The rest of the header is the test's business, but an ignored example has to be
+        // marked as such up front.
+        let ignore = text
+            .lines()
+            .take_while(|l| l.trim() != "---")
+            .filter_map(|l| l.trim().strip_prefix("ignore:"))
+            .map(|reason| format!("#[ignore = {:?}]\n", reason.trim()))
+            .next()
+            .unwrap_or_default();
+        tests.push_str(&format!(
+            "#[test]\n{}fn day{:02}_{}() {{\n    check({}, {:?});\n}}\n\n",
+            ignore,
+            day,
+            identifier(&name),
+            day,
+            path.display().to_string()
+        ));
+    }
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("samples.rs");
+    fs::write(out, tests).unwrap();
+
+    // Printouts start from the example of the same name.
+    let mut tests = String::new();
+    for (day, name, path) in day_files(&root.join("rounds")) {
+        let sample = samples.join(format!("{:02}", day)).join(&name);
+        tests.push_str(&format!(
+            "#[test]\nfn day{:02}_{}() {{\n    check({}, {:?}, {:?});\n}}\n\n",
+            day,
+            identifier(&name),
+            day,
+            sample.display().to_string(),
+            path.display().to_string()
+        ));
+    }
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("rounds.rs");
+    fs::write(out, tests).unwrap();
+}
+
+// Day, name and path of every file in the directories named after days.
+fn day_files(dir: &Path) -> Vec<(usize, String, std::path::PathBuf)> {
+    let mut files = vec![];
+    for (day, dir) in sorted_entries(dir) {
+        let day: usize = match day.parse() {
+            Ok(day) => day,
+            Err(_) => continue,
+        };
+        for (name, path) in sorted_entries(&dir) {
+            files.push((day, name, path));
+        }
+    }
+    files
+}
+
+// Names and paths of everything in a directory, by name; nothing if it isn't there.
+fn sorted_entries(dir: &Path) -> Vec<(String, std::path::PathBuf)> {
+    let mut entries: Vec<_> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .map(|e| e.unwrap())
+            .map(|e| (e.file_name().to_string_lossy().to_string(), e.path()))
+            .collect(),
+        Err(_) => vec![],
+    };
+    entries.sort();
+    entries
+}
+
+// Something that works as a part of a function name.
+fn identifier(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,305 @@
+// Benchmarking helpers: statistics over repeated runs, machine readable output, and comparing
+// against an earlier (baseline) run.
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::Duration;
+
+// Summary of a bunch of timings of the same thing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    // None if there's nothing to summarise.
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+        // Nearest rank: the smallest sample that's not faster than 95% of all the samples.
+        let p95 = sorted[(n * 95).div_ceil(100) - 1];
+        Some(Stats {
+            runs: n,
+            min: sorted[0],
+            median,
+            mean: sorted.iter().sum::<Duration>() / n as u32,
+            p95,
+        })
+    }
+}
+
+// Timings of one phase (parse, part1, part2 or total) of a day, for a single input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Measurement {
+    pub day: usize,
+    pub input: String,
+    pub phase: String,
+    pub stats: Stats,
+}
+
+// Picks a unit that makes the number readable; bench timings go from nanoseconds to seconds.
+pub fn format_duration(d: Duration) -> String {
+    let ns = d.as_nanos();
+    if ns < 1_000 {
+        format!("{}ns", ns)
+    } else if ns < 1_000_000 {
+        format!("{:.1}µs", ns as f64 / 1e3)
+    } else if ns < 1_000_000_000 {
+        format!("{:.1}ms", ns as f64 / 1e6)
+    } else {
+        format!("{:.2}s", ns as f64 / 1e9)
+    }
+}
+
+const CSV_HEADER: &str = "day,input,phase,runs,min_ns,median_ns,mean_ns,p95_ns";
+
+pub fn to_csv(measurements: &[Measurement]) -> String {
+    let mut csv = String::from(CSV_HEADER);
+    for m in measurements {
+        csv.push_str(&format!(
+            "\n{},{},{},{},{},{},{},{}",
+            m.day,
+            csv_field(&m.input),
+            csv_field(&m.phase),
+            m.stats.runs,
+            m.stats.min.as_nanos(),
+            m.stats.median.as_nanos(),
+            m.stats.mean.as_nanos(),
+            m.stats.p95.as_nanos()
+        ));
+    }
+    csv
+}
+
+// Quoted if need be, the way spreadsheets do it: a path could have a comma in it, or a quote.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+// Splits a line written with csv_field() back into fields.
+fn csv_fields(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = vec![];
+    let mut chars = line.chars().peekable();
+    loop {
+        let mut field = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some('"') if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    Some('"') => break,
+                    Some(c) => field.push(c),
+                    None => return Err("a closing quote".to_string()),
+                }
+            }
+        }
+        while let Some(&c) = chars.peek() {
+            if c == ',' {
+                break;
+            }
+            field.push(c);
+            chars.next();
+        }
+        fields.push(field);
+        if chars.next().is_none() {
+            return Ok(fields);
+        }
+    }
+}
+
+// Not worth pulling in serde just for this.
+pub fn json_string(s: &str) -> String {
+    let mut json = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+pub fn to_json(measurements: &[Measurement]) -> String {
+    let objects: Vec<String> = measurements
+        .iter()
+        .map(|m| {
+            format!(
+                "  {{\"day\": {}, \"input\": {}, \"phase\": {}, \"runs\": {}, \"min_ns\": {}, \
+                 \"median_ns\": {}, \"mean_ns\": {}, \"p95_ns\": {}}}",
+                m.day,
+                json_string(&m.input),
+                json_string(&m.phase),
+                m.stats.runs,
+                m.stats.min.as_nanos(),
+                m.stats.median.as_nanos(),
+                m.stats.mean.as_nanos(),
+                m.stats.p95.as_nanos()
+            )
+        })
+        .collect();
+    format!("[\n{}\n]", objects.join(",\n"))
+}
+
+// Medians from an earlier benchmark, saved with --format csv.
+#[derive(Debug, Default)]
+pub struct Baseline {
+    medians: HashMap<(usize, String, String), Duration>,
+}
+
+impl Baseline {
+    pub fn median(&self, day: usize, input: &str, phase: &str) -> Option<Duration> {
+        self.medians
+            .get(&(day, input.to_string(), phase.to_string()))
+            .cloned()
+    }
+
+    // How much slower (positive) or faster (negative) the measurement got, relative to the
+    // baseline: 0.1 means 10% slower. None if there's nothing to compare with.
+    pub fn change(&self, m: &Measurement) -> Option<f64> {
+        let before = self.median(m.day, &m.input, &m.phase)?.as_secs_f64();
+        if before == 0.0 {
+            return None;
+        }
+        Some(m.stats.median.as_secs_f64() / before - 1.0)
+    }
+}
+
+impl FromStr for Baseline {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut medians = HashMap::new();
+        for (n, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line == CSV_HEADER {
+                continue;
+            }
+            let fields = csv_fields(line).map_err(|e| format!("line {}: expected {}", n + 1, e))?;
+            if fields.len() != 8 {
+                return Err(format!("line {}: expected '{}'", n + 1, CSV_HEADER));
+            }
+            let day = fields[0]
+                .parse()
+                .map_err(|_| format!("line {}: '{}' is not a day", n + 1, fields[0]))?;
+            let median = fields[5]
+                .parse()
+                .map_err(|_| format!("line {}: '{}' is not a median", n + 1, fields[5]))?;
+            medians.insert(
+                (day, fields[1].clone(), fields[2].clone()),
+                Duration::from_nanos(median),
+            );
+        }
+        Ok(Baseline { medians })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: &[u64]) -> Vec<Duration> {
+        ms.iter().map(|&m| Duration::from_millis(m)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(Stats::from_samples(&[]), None);
+        let stats = Stats::from_samples(&ms(&[5, 1, 3, 2, 4])).unwrap();
+        assert_eq!(stats.runs, 5);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+        let stats = Stats::from_samples(&ms(&[4, 1, 2, 3])).unwrap();
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        let stats = Stats::from_samples(&ms(&(1..=100).collect::<Vec<_>>())).unwrap();
+        assert_eq!(stats.p95, Duration::from_millis(95));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(767)), "767ns");
+        assert_eq!(format_duration(Duration::from_nanos(110_285)), "110.3µs");
+        assert_eq!(format_duration(Duration::from_millis(17)), "17.0ms");
+        assert_eq!(format_duration(Duration::from_millis(2500)), "2.50s");
+    }
+
+    #[test]
+    fn test_baseline() {
+        let m = Measurement {
+            day: 9,
+            input: "inputs/09".to_string(),
+            phase: "part2".to_string(),
+            stats: Stats::from_samples(&ms(&[12])).unwrap(),
+        };
+        let csv = to_csv(std::slice::from_ref(&m));
+        assert_eq!(
+            csv,
+            "day,input,phase,runs,min_ns,median_ns,mean_ns,p95_ns\n\
+             9,inputs/09,part2,1,12000000,12000000,12000000,12000000"
+        );
+        let baseline: Baseline = "9,inputs/09,part2,1,10000000,10000000,10000000,10000000"
+            .parse()
+            .unwrap();
+        let change = baseline.change(&m).unwrap();
+        assert!((change - 0.2).abs() < 1e-9);
+        assert_eq!(baseline.median(9, "inputs/09", "part1"), None);
+        assert!("9,inputs/09,part2".parse::<Baseline>().is_err());
+    }
+
+    #[test]
+    fn test_csv_quoting() {
+        let m = Measurement {
+            day: 7,
+            input: "inputs/07/alice, \"bob\"".to_string(),
+            phase: "total".to_string(),
+            stats: Stats::from_samples(&ms(&[5])).unwrap(),
+        };
+        let csv = to_csv(std::slice::from_ref(&m));
+        assert!(csv.ends_with(
+            "\n7,\"inputs/07/alice, \"\"bob\"\"\",total,1,5000000,5000000,5000000,5000000"
+        ));
+        let baseline: Baseline = csv.parse().unwrap();
+        assert_eq!(
+            baseline.median(7, &m.input, "total"),
+            Some(Duration::from_millis(5))
+        );
+        assert!("7,\"inputs/07,total,1,5,5,5,5".parse::<Baseline>().is_err());
+    }
+
+    #[test]
+    fn test_json() {
+        let m = Measurement {
+            day: 1,
+            input: "a \"b\"".to_string(),
+            phase: "parse".to_string(),
+            stats: Stats::from_samples(&[Duration::from_nanos(7)]).unwrap(),
+        };
+        assert_eq!(
+            to_json(&[m]),
+            "[\n  {\"day\": 1, \"input\": \"a \\\"b\\\"\", \"phase\": \"parse\", \"runs\": 1, \
+             \"min_ns\": 7, \"median_ns\": 7, \"mean_ns\": 7, \"p95_ns\": 7}\n]"
+        );
+    }
+}
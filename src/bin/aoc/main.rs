@@ -0,0 +1,864 @@
+use aoc::bench::{self, Baseline, Measurement, Stats};
+#[cfg(feature = "render")]
+use aoc::render::Renderer;
+use aoc::viewer::{self, Scene, Viewer};
+use aoc::*;
+#[cfg(feature = "serde")]
+use aoc::{simulation, snapshot};
+use std::env;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+mod scaffold;
+
+const USAGE: &str =
+    "usage: aoc <day> [--part <1|2>] [--input <file|dir|->] [--spans <table|csv|json>]
+                 [--param <name=value>]...
+       aoc all [--threads <n>]
+       aoc verify [day]
+       aoc bench [day] [--runs <n>] [--warmup <n>] [--format <table|csv|json>]
+                 [--baseline <csv>] [--threshold <percent>]
+       aoc new <day>
+       aoc watch <13|15> [--input <file>] [--steps <n>]
+       aoc snapshot <4|7|8|13|15> [--input <file>] [--steps <n>]
+       aoc animate <10|12|13|15> [--input <file>] [--steps <n>] [--output <gif>]
+       (any of these) [--log <filter>]
+
+Inputs are read from $AOC_INPUTS/NN (inputs/NN by default). If that's a directory, every file
+inside it is solved separately; '-' reads the input from stdin.
+
+--param sets a puzzle parameter that isn't part of the input, for when yours differs from the
+one the puzzle gave me: workers=5 and cost=60 on day 7, safe_area_within=10000 on day 6. It
+can be given more than once.
+
+--spans prints where the time went (parsing, each part, and any spans a day times by itself)
+to stderr.
+
+all solves every day, a few at a time (one per core, unless --threads says otherwise), and
+prints all the answers in one table, in order of days.
+
+--log shows what the days have to say on the side, on stderr: error, info (the default), debug
+or trace, for all days, and then some for single days. AOC_LOG works too:
+
+  aoc 15 --log info,15=debug
+
+bench solves every input many times over and reports timings of each phase. Save the results
+with --format csv to compare later runs against them with --baseline; anything slower than the
+baseline by more than --threshold (10% by default) is reported as a regression.
+
+watch plays back a day's simulation in the terminal: the mine's carts on day 13, the battle on
+day 15. Step forwards and backwards, go to any step, or skip to the next crash or death; h
+lists the commands. Only the first --steps steps (1000 by default) get recorded.
+
+snapshot prints a day's parsed input as JSON, or its simulation (days 13 and 15) after --steps
+steps; see aoc::snapshot for loading it back. Needs the serde feature.
+
+animate saves a day's simulation as an animated GIF, aocNN.gif in the temp directory unless
+--output says otherwise: the first --steps steps (1000 by default) of the pots, the carts or the
+battle, or day 10's stars coming together around the message. Needs the render feature.
+
+new sets up a day that isn't there yet: its module made from the skeleton, an empty input, an
+example to fill in under samples/NN, and an entry in the list of days. It has to be compiled in
+afterwards.";
+
+// Known good answers live here; see aoc::Answers for the format.
+const ANSWERS_PATH: &str = "answers.txt";
+// Where to look for puzzle inputs, unless AOC_INPUTS says otherwise.
+const DEFAULT_INPUTS: &str = "inputs";
+
+fn input_path(day: usize) -> String {
+    let base = env::var("AOC_INPUTS").unwrap_or_else(|_| DEFAULT_INPUTS.to_string());
+    format!("{}/{:02}", base, day)
+}
+
+fn format_elapsed(d: Duration) -> String {
+    format!("{}.{:06}s", d.as_secs(), d.subsec_micros())
+}
+
+// Why a day couldn't be solved for an input.
+enum Failure {
+    Read(String),
+    // Keeps the input around, to show the offending line.
+    Parse(ParseError, String),
+    // Anything else that went wrong while solving.
+    Solve(Error),
+    // The day blew up; only ever caught when solving all the days at once, so that one day can't
+    // take the rest down with it.
+    Panic(String),
+}
+
+impl Failure {
+    fn new(e: Error, text: String) -> Failure {
+        match e {
+            Error::Parse(e) => Failure::Parse(e, text),
+            e => Failure::Solve(e),
+        }
+    }
+
+    // Fits in a single line of a table.
+    fn summary(&self) -> String {
+        match self {
+            Failure::Read(e) => e.clone(),
+            Failure::Parse(e, _) => e.to_string(),
+            Failure::Solve(e) => e.to_string(),
+            Failure::Panic(message) => format!("panicked: {}", message),
+        }
+    }
+
+    fn details(&self, input: &str) -> String {
+        match self {
+            Failure::Read(e) => e.clone(),
+            Failure::Parse(e, text) => e.diagnostic(input, text),
+            Failure::Solve(e) => format!("error: {}\n --> {}", e, input),
+            Failure::Panic(message) => format!("panicked: {}\n --> {}", message, input),
+        }
+    }
+}
+
+// A single day solved for a single input; or the reason why it couldn't be.
+struct Run {
+    input: String,
+    result: Result<Report, Failure>,
+}
+
+// Solves given day for every input found under path.
+fn solve(solution: &dyn Runnable, path: &str, part: Option<usize>, params: &Params) -> Vec<Run> {
+    let inputs = match list_inputs(path) {
+        Ok(inputs) => inputs,
+        Err(e) => {
+            return vec![Run {
+                input: path.to_string(),
+                result: Err(Failure::Read(format!("{}: {}", path, e))),
+            }]
+        }
+    };
+    inputs
+        .into_iter()
+        .map(|input| {
+            let result = match read_file(&input) {
+                Ok(text) => solution
+                    .run_with(&text, part, params)
+                    .map_err(|e| Failure::new(e, text)),
+                Err(e) => Err(Failure::Read(format!("{}: {}", input, e))),
+            };
+            Run { input, result }
+        })
+        .collect()
+}
+
+// Prints the span tree of a run, to stderr so it doesn't get mixed up with the answers.
+fn print_spans(spans: &Timing, format: &Format) {
+    match format {
+        Format::Table => {
+            let rows: Vec<Vec<String>> = spans
+                .flatten()
+                .into_iter()
+                .map(|(path, t)| {
+                    let depth = path.matches('/').count();
+                    let share = 100.0 * t.elapsed.as_secs_f64() / spans.elapsed.as_secs_f64();
+                    vec![
+                        format!("{}{}", "  ".repeat(depth), t.name),
+                        t.calls.to_string(),
+                        bench::format_duration(t.elapsed),
+                        format!("{:.1}%", share),
+                    ]
+                })
+                .collect();
+            eprint!(
+                "{}",
+                format_table(&["Span", "Calls", "Time", "Share"], &rows)
+            );
+        }
+        Format::Csv => eprintln!("{}", spans.to_csv()),
+        Format::Json => eprintln!("{}", spans.to_json()),
+    }
+}
+
+// Returns false if any of the inputs couldn't be solved.
+fn run_one(
+    day: usize,
+    solution: &dyn Runnable,
+    path: &str,
+    part: Option<usize>,
+    params: &Params,
+    spans: Option<Format>,
+) -> bool {
+    let runs = solve(solution, path, part, params);
+    // With more than one input, tell the answers apart.
+    let label = |input: &str| {
+        if runs.len() > 1 {
+            format!(" [{}]", input)
+        } else {
+            String::new()
+        }
+    };
+    let mut ok = true;
+    for run in &runs {
+        match &run.result {
+            Ok(report) => {
+                if let Some(answer) = &report.part1 {
+                    println!("Day {}, part 1{}: {}", day, label(&run.input), answer);
+                }
+                if let Some(answer) = &report.part2 {
+                    println!("Day {}, part 2{}: {}", day, label(&run.input), answer);
+                }
+                match &spans {
+                    Some(format) => print_spans(&report.spans, format),
+                    None => eprintln!("Done in {}", format_elapsed(report.elapsed)),
+                }
+            }
+            Err(e) => {
+                eprintln!("Day {}: {}", day, e.details(&run.input));
+                ok = false;
+            }
+        }
+    }
+    ok
+}
+
+// Solves a day for all its inputs, like solve(), but turns a panic into a failure.
+fn solve_safely(day: usize, solution: &dyn Runnable) -> Vec<Run> {
+    let path = input_path(day);
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        solve(solution, &path, None, &Params::default())
+    }))
+    .unwrap_or_else(|e| {
+        // Panics carry either a &str or a String; anything else is anyone's guess.
+        let message = match (e.downcast_ref::<&str>(), e.downcast_ref::<String>()) {
+            (Some(s), _) => s.to_string(),
+            (_, Some(s)) => s.clone(),
+            _ => "who knows why".to_string(),
+        };
+        vec![Run {
+            input: path,
+            result: Err(Failure::Panic(message)),
+        }]
+    })
+}
+
+// Answers drawn over many lines (day 10's, without the render feature) don't fit in a table.
+fn table_answer(day: usize, answer: String) -> String {
+    if answer.contains('\n') {
+        format!("(see aoc {})", day)
+    } else {
+        answer
+    }
+}
+
+// Returns false if any of the days couldn't be solved.
+fn run_all(threads: usize) -> bool {
+    let clock = Instant::now();
+    // The pool hands results back in order of days, however long each of them took.
+    let solved = pool::map(days(), threads, |(day, solution)| {
+        (day, solve_safely(day, solution.as_ref()))
+    });
+    let elapsed = clock.elapsed();
+    let mut rows = vec![];
+    let mut ok = true;
+    for (day, runs) in solved {
+        for run in runs {
+            let mut row = vec![day.to_string(), run.input];
+            match run.result {
+                Ok(report) => {
+                    row.push(table_answer(day, report.part1.clone().unwrap_or_default()));
+                    row.push(table_answer(day, report.part2.clone().unwrap_or_default()));
+                    row.push(format_elapsed(report.elapsed));
+                }
+                Err(e) => {
+                    row.push(format!("error: {}", e.summary()));
+                    ok = false;
+                }
+            }
+            rows.push(row);
+        }
+    }
+    // Days run side by side, so the total is less than the times above added up.
+    rows.push(vec![
+        "all".to_string(),
+        format!("threads: {}", threads),
+        String::new(),
+        String::new(),
+        format_elapsed(elapsed),
+    ]);
+    print_table(&["Day", "Input", "Part 1", "Part 2", "Time"], &rows);
+    ok
+}
+
+// How does an answer compare to the one we know is right?
+enum Verdict {
+    Pass,
+    Fail,
+    Missing,
+    // There's an answer, but no way of checking it; see Answers::UNCHECKED.
+    Unchecked,
+}
+
+// The verdict, and what to put in the table for it.
+fn check(day: usize, expected: Option<&str>, got: &str) -> (Verdict, String) {
+    let shown = table_answer(day, got.to_string());
+    match expected {
+        Some(Answers::UNCHECKED) => (Verdict::Unchecked, format!("unchecked: got {}", shown)),
+        Some(e) if e == got => (Verdict::Pass, "ok".to_string()),
+        Some(e) => (
+            Verdict::Fail,
+            format!("FAIL: got {}, expected {}", shown, e),
+        ),
+        None => (Verdict::Missing, format!("missing: got {}", shown)),
+    }
+}
+
+// Solves the given days and compares the answers with answers.txt. Returns false if any of them
+// was wrong, or couldn't be worked out at all.
+fn verify(days: Days) -> bool {
+    let answers: Answers = read_file(ANSWERS_PATH)
+        .map_err(|e| e.to_string())
+        .and_then(|a| a.parse())
+        .unwrap_or_else(|e| fail(&format!("{}: {}", ANSWERS_PATH, e)));
+    let (mut passed, mut failed, mut missing, mut unchecked) = (0, 0, 0, 0);
+    let mut rows = vec![];
+    for (day, solution) in days {
+        for run in solve(
+            solution.as_ref(),
+            &input_path(day),
+            None,
+            &Params::default(),
+        ) {
+            let mut row = vec![day.to_string(), run.input.clone()];
+            let report = match run.result {
+                Ok(report) => report,
+                Err(e) => {
+                    failed += 1;
+                    row.push(format!("error: {}", e.summary()));
+                    rows.push(row);
+                    continue;
+                }
+            };
+            for (part, answer) in [(1, &report.part1), (2, &report.part2)].iter() {
+                let (verdict, cell) = check(
+                    day,
+                    answers.get(day, *part, &run.input),
+                    answer.as_ref().unwrap(),
+                );
+                match verdict {
+                    Verdict::Pass => passed += 1,
+                    Verdict::Fail => failed += 1,
+                    Verdict::Missing => missing += 1,
+                    Verdict::Unchecked => unchecked += 1,
+                }
+                row.push(cell);
+            }
+            row.push(format_elapsed(report.elapsed));
+            rows.push(row);
+        }
+    }
+    print_table(&["Day", "Input", "Part 1", "Part 2", "Time"], &rows);
+    println!(
+        "\n{} passed, {} failed, {} missing, {} unchecked",
+        passed, failed, missing, unchecked
+    );
+    failed == 0
+}
+
+// How bench and --spans should print the results.
+enum Format {
+    Table,
+    Csv,
+    Json,
+}
+
+struct BenchOptions {
+    runs: usize,
+    // Runs thrown away before we start measuring, to warm up the caches and such.
+    warmup: usize,
+    format: Format,
+    baseline: Option<Baseline>,
+    // How much slower than the baseline is still fine; 0.1 is 10%.
+    threshold: f64,
+}
+
+// A phase we measure, and where to find its timing in a Report.
+type Phase = (&'static str, fn(&Report) -> Duration);
+
+const PHASES: [Phase; 4] = [
+    ("parse", |r| r.spans.time("parse")),
+    ("part1", |r| r.spans.time("part1")),
+    ("part2", |r| r.spans.time("part2")),
+    ("total", |r| r.elapsed),
+];
+
+// Solves a single input over and over again.
+fn bench_input(
+    day: usize,
+    solution: &dyn Runnable,
+    input: &str,
+    options: &BenchOptions,
+) -> Result<Vec<Measurement>, Failure> {
+    let text = read_file(input).map_err(|e| Failure::Read(format!("{}: {}", input, e)))?;
+    let mut reports = vec![];
+    for run in 0..options.warmup + options.runs {
+        match solution.run(&text, None) {
+            Ok(report) if run >= options.warmup => reports.push(report),
+            Ok(_) => (),
+            Err(e) => return Err(Failure::new(e, text)),
+        }
+    }
+    Ok(PHASES
+        .iter()
+        .map(|(phase, time)| Measurement {
+            day,
+            input: input.to_string(),
+            phase: phase.to_string(),
+            stats: Stats::from_samples(&reports.iter().map(time).collect::<Vec<_>>()).unwrap(),
+        })
+        .collect())
+}
+
+// Benchmarks the given days. Returns false if any of them couldn't be solved, or got slower than
+// the baseline allows.
+fn bench(days: Days, options: &BenchOptions) -> bool {
+    let mut ok = true;
+    let mut measurements = vec![];
+    for (day, solution) in days {
+        let path = input_path(day);
+        let inputs = list_inputs(&path).unwrap_or_else(|e| {
+            eprintln!("Day {}: {}: {}", day, path, e);
+            ok = false;
+            vec![]
+        });
+        for input in inputs {
+            match bench_input(day, solution.as_ref(), &input, options) {
+                Ok(m) => measurements.extend(m),
+                Err(e) => {
+                    eprintln!("Day {}: {}", day, e.details(&input));
+                    ok = false;
+                }
+            }
+        }
+    }
+
+    let mut rows = vec![];
+    for m in &measurements {
+        let mut row = vec![
+            m.day.to_string(),
+            m.input.clone(),
+            m.phase.clone(),
+            bench::format_duration(m.stats.min),
+            bench::format_duration(m.stats.median),
+            bench::format_duration(m.stats.mean),
+            bench::format_duration(m.stats.p95),
+        ];
+        if let Some(baseline) = &options.baseline {
+            let cell = match baseline.change(m) {
+                Some(change) if change > options.threshold => {
+                    eprintln!(
+                        "Day {} [{}] {}: {:.1}% slower than the baseline",
+                        m.day,
+                        m.input,
+                        m.phase,
+                        change * 100.0
+                    );
+                    ok = false;
+                    format!("{:+.1}% REGRESSION", change * 100.0)
+                }
+                Some(change) => format!("{:+.1}%", change * 100.0),
+                None => "-".to_string(),
+            };
+            row.push(cell);
+        }
+        rows.push(row);
+    }
+    match options.format {
+        Format::Table => {
+            let mut header = vec!["Day", "Input", "Phase", "Min", "Median", "Mean", "P95"];
+            if options.baseline.is_some() {
+                header.push("Change");
+            }
+            print_table(&header, &rows);
+        }
+        Format::Csv => println!("{}", bench::to_csv(&measurements)),
+        Format::Json => println!("{}", bench::to_json(&measurements)),
+    }
+    ok
+}
+
+// Lays out rows as columns aligned to the widest cell.
+fn format_table(header: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(cell.chars().count());
+        }
+    }
+    let format_row = |cells: Vec<&str>| -> String {
+        cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(c, &w)| format!("{:w$}", c, w = w))
+            .collect::<Vec<_>>()
+            .join(" | ")
+            .trim_end()
+            .to_string()
+    };
+    let mut table = format_row(header.to_vec()) + "\n";
+    table += &widths
+        .iter()
+        .map(|&w| "-".repeat(w))
+        .collect::<Vec<_>>()
+        .join("-+-");
+    table += "\n";
+    for row in rows {
+        table += &format_row(row.iter().map(|c| c.as_str()).collect());
+        table += "\n";
+    }
+    table
+}
+
+fn print_table(header: &[&str], rows: &[Vec<String>]) {
+    print!("{}", format_table(header, rows));
+}
+
+// Steps of a day's simulation, for watching. Only some days have anything worth watching.
+fn record_scenes(day: usize, path: &str, steps: usize) -> Result<Vec<Scene>, Failure> {
+    let text = read_file(path).map_err(|e| Failure::Read(format!("{}: {}", path, e)))?;
+    let scenes = match day {
+        13 => day13::parse_input(&text).map(|mut mine| viewer::record(&mut mine, steps)),
+        15 => day15::Arena::from_str(&text, 3).map(|mut arena| viewer::record(&mut arena, steps)),
+        _ => fail(&format!("Day {} can't be watched; try 13 or 15.", day)),
+    };
+    scenes.map_err(|e| Failure::new(e.into(), text))
+}
+
+// A day's parsed input as JSON, or its simulation after some steps.
+#[cfg(feature = "serde")]
+fn take_snapshot(day: usize, path: &str, steps: usize) -> Result<String, Failure> {
+    let text = read_file(path).map_err(|e| Failure::Read(format!("{}: {}", path, e)))?;
+    let json = || -> Result<String, Error> {
+        match day {
+            4 => snapshot::to_json(&day04::parse_input(&text)?),
+            7 => snapshot::to_json(&day07::parse_input(&text)?),
+            8 => snapshot::to_json(&day08::parse_input(&text)?),
+            13 => {
+                let mut mine = day13::parse_input(&text)?;
+                simulation::run_n(&mut mine, steps);
+                snapshot::to_json(&mine)
+            }
+            15 => {
+                let mut arena = day15::Arena::from_str(&text, 3)?;
+                simulation::run_n(&mut arena, steps);
+                snapshot::to_json(&arena)
+            }
+            _ => fail(&format!(
+                "Day {} can't be saved; try 4, 7, 8, 13 or 15.",
+                day
+            )),
+        }
+    };
+    json().map_err(|e| Failure::new(e, text.clone()))
+}
+
+// A day's simulation as an animated GIF; returns how many frames it took.
+#[cfg(feature = "render")]
+fn make_animation(day: usize, path: &str, steps: usize, output: &str) -> Result<usize, Failure> {
+    let text = read_file(path).map_err(|e| Failure::Read(format!("{}: {}", path, e)))?;
+    let renderer = Renderer::new();
+    let animate = || -> Result<usize, Error> {
+        let frames = match day {
+            10 => day10::animate(&day10::parse_input(&text)?, &renderer, output),
+            12 => renderer.animate(&mut day12::parse_input(&text)?, steps, output),
+            13 => renderer.animate(&mut day13::parse_input(&text)?, steps, output),
+            15 => renderer.animate(&mut day15::Arena::from_str(&text, 3)?, steps, output),
+            _ => fail(&format!(
+                "Day {} can't be animated; try 10, 12, 13 or 15.",
+                day
+            )),
+        };
+        frames.map_err(|e| Error::Render(format!("{}: {}", output, e)))
+    };
+    animate().map_err(|e| Failure::new(e, text.clone()))
+}
+
+// Day, input and number of steps to watch, or to take before a snapshot.
+fn parse_steps_args(args: &[String], default_steps: usize) -> (usize, String, usize) {
+    let day: usize = match args.first() {
+        Some(d) => d
+            .parse()
+            .unwrap_or_else(|_| fail(&format!("'{}' is not a day.", d))),
+        None => fail("Which day?"),
+    };
+    let mut path = input_path(day);
+    let mut steps = default_steps;
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--input" | "-i" => match rest.next() {
+                Some(p) => path = p.to_string(),
+                None => fail("--input needs a path."),
+            },
+            "--steps" => steps = option_value(arg, rest.next()),
+            a => fail(&format!("Unknown argument '{}'.", a)),
+        }
+    }
+    (day, path, steps)
+}
+
+// Value of a command line option, parsed.
+fn option_value<T: FromStr>(option: &str, value: Option<&String>) -> T {
+    value
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_else(|| fail(&format!("{} needs a number.", option)))
+}
+
+fn parse_format(option: &str, value: Option<&String>) -> Format {
+    match value.map(|f| f.as_str()) {
+        Some("table") => Format::Table,
+        Some("csv") => Format::Csv,
+        Some("json") => Format::Json,
+        _ => fail(&format!(
+            "{} needs to be one of table, csv or json.",
+            option
+        )),
+    }
+}
+
+fn parse_bench_args(args: &[String]) -> (Days, BenchOptions) {
+    let mut options = BenchOptions {
+        runs: 10,
+        warmup: 1,
+        format: Format::Table,
+        baseline: None,
+        threshold: 0.1,
+    };
+    let mut days = days();
+    let mut rest = args.iter().peekable();
+    if let Some(day) = rest.peek().and_then(|d| d.parse::<usize>().ok()) {
+        rest.next();
+        days.retain(|(d, _)| *d == day);
+        if days.is_empty() {
+            fail(&format!("Day {} isn't solved (yet?).", day));
+        }
+    }
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--runs" | "-n" => options.runs = option_value(arg, rest.next()),
+            "--warmup" | "-w" => options.warmup = option_value(arg, rest.next()),
+            "--threshold" | "-t" => {
+                options.threshold = option_value::<f64>(arg, rest.next()) / 100.0
+            }
+            "--format" | "-f" => options.format = parse_format(arg, rest.next()),
+            "--baseline" | "-b" => {
+                let path = rest
+                    .next()
+                    .unwrap_or_else(|| fail("--baseline needs a path."));
+                let baseline = read_file(path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|b| b.parse())
+                    .unwrap_or_else(|e| fail(&format!("{}: {}", path, e)));
+                options.baseline = Some(baseline);
+            }
+            a => fail(&format!("Unknown argument '{}'.", a)),
+        }
+    }
+    if options.runs == 0 {
+        fail("--runs needs to be at least 1.");
+    }
+    (days, options)
+}
+
+// The log filter comes from --log, which works with every command, or from AOC_LOG. Takes --log
+// out of args, so that the commands don't have to know about it.
+fn log_filter(args: &mut Vec<String>) -> log::Filter {
+    let spec = match args.iter().position(|a| a == "--log") {
+        Some(i) if i + 1 < args.len() => args.drain(i..i + 2).nth(1),
+        Some(_) => fail("--log needs a filter."),
+        None => env::var("AOC_LOG").ok(),
+    };
+    spec.map_or_else(log::Filter::default, |spec| {
+        spec.parse()
+            .unwrap_or_else(|e| fail(&format!("Bad log filter: {}.", e)))
+    })
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}\n{}", message, USAGE);
+    process::exit(2);
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    log::set_filter(log_filter(&mut args));
+    if args.is_empty() {
+        fail("Which day?");
+    }
+    if args[0] == "all" {
+        let threads = match &args[1..] {
+            [] => pool::default_threads(),
+            [option, n] if option == "--threads" || option == "-t" => {
+                option_value("--threads", Some(n))
+            }
+            _ => fail("'all' only takes --threads."),
+        };
+        if !run_all(threads) {
+            process::exit(1);
+        }
+        return;
+    }
+    if args[0] == "verify" {
+        let days = match args.get(1) {
+            None => days(),
+            Some(d) => {
+                let day: usize = d
+                    .parse()
+                    .unwrap_or_else(|_| fail(&format!("'{}' is not a day.", d)));
+                days().into_iter().filter(|(d, _)| *d == day).collect()
+            }
+        };
+        if days.is_empty() || args.len() > 2 {
+            fail("Nothing to verify.");
+        }
+        if !verify(days) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if args[0] == "new" {
+        let day: usize = match &args[1..] {
+            [d] => d
+                .parse()
+                .unwrap_or_else(|_| fail(&format!("'{}' is not a day.", d))),
+            _ => fail("'new' needs a day, and nothing else."),
+        };
+        match scaffold::new_day(day, &input_path(day)) {
+            Ok(created) => {
+                for path in created {
+                    println!("Created {}", path);
+                }
+            }
+            Err(e) => {
+                eprintln!("Day {}: {}", day, e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if args[0] == "watch" {
+        let (day, path, steps) = parse_steps_args(&args[1..], 1000);
+        let scenes = record_scenes(day, &path, steps).unwrap_or_else(|e| {
+            eprintln!("Day {}: {}", day, e.details(&path));
+            process::exit(1);
+        });
+        if let Err(e) = Viewer::new(scenes).run(io::stdin().lock(), io::stdout()) {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    if args[0] == "snapshot" {
+        let (day, path, steps) = parse_steps_args(&args[1..], 0);
+        #[cfg(not(feature = "serde"))]
+        fail(&format!(
+            "Can't save day {} ({} after {} steps) without the serde feature; build with \
+             --features serde.",
+            day, path, steps
+        ));
+        #[cfg(feature = "serde")]
+        {
+            match take_snapshot(day, &path, steps) {
+                Ok(json) => println!("{}", json),
+                Err(e) => {
+                    eprintln!("Day {}: {}", day, e.details(&path));
+                    process::exit(1);
+                }
+            }
+            return;
+        }
+    }
+
+    if args[0] == "animate" {
+        // --output is animate's own; the rest is the same as for watch.
+        let mut args = args[1..].to_vec();
+        let output = match args.iter().position(|a| a == "--output" || a == "-o") {
+            Some(i) if i + 1 < args.len() => {
+                let output = args.remove(i + 1);
+                args.remove(i);
+                Some(output)
+            }
+            Some(_) => fail("--output needs a path."),
+            None => None,
+        };
+        let (day, path, steps) = parse_steps_args(&args, 1000);
+        let output = output.unwrap_or_else(|| {
+            let gif = env::temp_dir().join(format!("aoc{:02}.gif", day));
+            gif.to_string_lossy().to_string()
+        });
+        #[cfg(not(feature = "render"))]
+        fail(&format!(
+            "Can't animate day {} ({} for {} steps, into {}) without the render feature; build \
+             with --features render.",
+            day, path, steps, output
+        ));
+        #[cfg(feature = "render")]
+        {
+            match make_animation(day, &path, steps, &output) {
+                Ok(frames) => println!("Saved {} frames to {}", frames, output),
+                Err(e) => {
+                    eprintln!("Day {}: {}", day, e.details(&path));
+                    process::exit(1);
+                }
+            }
+            return;
+        }
+    }
+
+    if args[0] == "bench" {
+        let (days, options) = parse_bench_args(&args[1..]);
+        if !bench(days, &options) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let day: usize = args[0]
+        .parse()
+        .unwrap_or_else(|_| fail(&format!("'{}' is not a day.", args[0])));
+    let mut part = None;
+    let mut path = None;
+    let mut spans = None;
+    let mut params = Params::default();
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--part" | "-p" => {
+                part = match rest.next().map(|p| p.as_str()) {
+                    Some("1") => Some(1),
+                    Some("2") => Some(2),
+                    _ => fail("--part needs to be either 1 or 2."),
+                }
+            }
+            "--input" | "-i" => match rest.next() {
+                Some(p) => path = Some(p.to_string()),
+                None => fail("--input needs a path."),
+            },
+            "--spans" | "-s" => spans = Some(parse_format(arg, rest.next())),
+            "--param" | "-P" => match rest.next().and_then(|p| p.split_once('=')) {
+                Some((name, value)) => params.set(name.trim(), value.trim()),
+                None => fail("--param needs a name=value."),
+            },
+            a => fail(&format!("Unknown argument '{}'.", a)),
+        }
+    }
+    match days().into_iter().find(|(d, _)| *d == day) {
+        Some((day, solution)) => {
+            let path = path.unwrap_or_else(|| input_path(day));
+            if !run_one(day, solution.as_ref(), &path, part, &params, spans) {
+                process::exit(1);
+            }
+        }
+        None => fail(&format!("Day {} isn't solved (yet?).", day)),
+    }
+}
@@ -0,0 +1,178 @@
+// Sets up a new day: a module made from the skeleton, an empty input to paste the puzzle input
+// into, an example to fill in, and the day registered with the runner; everything that used to be
+// copied by hand.
+use std::fs;
+use std::path::Path;
+
+// Where the days' sources live; this is a tool for working on the sources, after all.
+const SOURCES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+// Where examples live; see aoc::examples.
+const SAMPLES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/samples");
+
+// The example every new day starts with. It's ignored until it's filled in, so that an untouched
+// day doesn't break the build.
+const EXAMPLE: &str =
+    "# Paste the example from the puzzle below the line, fill in the answers it should give, and
+# drop the ignore line.
+ignore: needs the example from the puzzle
+part1:
+part2:
+---
+";
+
+fn module(day: usize) -> String {
+    format!("day{:02}", day)
+}
+
+fn solution(day: usize) -> String {
+    format!("Day{:02}", day)
+}
+
+// The new day's source: the skeleton, renamed, without the skeleton's own tests. The day's tests
+// start out as an example file instead.
+fn generate(day: usize, skel: &str) -> Result<String, String> {
+    let code = match skel.find("#[cfg(test)]") {
+        Some(i) => skel[..i].trim_end(),
+        None => skel.trim_end(),
+    };
+    if !code.contains("Skel") {
+        return Err("the skeleton doesn't have a Skel to rename".to_string());
+    }
+    Ok(format!("{}\n", code.replace("Skel", &solution(day))))
+}
+
+// Adds the day to lib.rs: its mod line, and an entry in days(). Both lists are kept in order.
+fn register(day: usize, lib: &str) -> Result<String, String> {
+    let module = module(day);
+    let mod_line = format!("pub mod {};", module);
+    let entry = format!(
+        "        ({}, Box::new({}::{})),",
+        day,
+        module,
+        solution(day)
+    );
+    let lines: Vec<&str> = lib.lines().collect();
+    if lines.contains(&mod_line.as_str()) {
+        return Err(format!("day {} is already registered", day));
+    }
+    // Both lists are sorted, and all of their lines look the same, so a new line goes right
+    // before the first one that sorts after it; or after the last one, if none of them does.
+    let position = |is_item: &dyn Fn(&str) -> bool, new: &str| {
+        let items: Vec<usize> = (0..lines.len()).filter(|&i| is_item(lines[i])).collect();
+        let before = items
+            .iter()
+            .find(|&&i| day_of(lines[i]) > day_of(new))
+            .cloned();
+        before.or_else(|| items.last().map(|i| i + 1))
+    };
+    let is_mod = |l: &str| l.starts_with("pub mod day") && l.ends_with(';');
+    let is_entry = |l: &str| l.trim_start().starts_with('(') && l.contains("Box::new(day");
+    let mod_at = position(&is_mod, &mod_line).ok_or("lib.rs has no pub mod dayNN lines")?;
+    let entry_at = position(&is_entry, &entry).ok_or("lib.rs has no days() list")?;
+    let mut result = vec![];
+    for (i, line) in lines.iter().enumerate() {
+        if i == mod_at {
+            result.push(mod_line.as_str());
+        }
+        if i == entry_at {
+            result.push(entry.as_str());
+        }
+        result.push(line);
+    }
+    Ok(result.join("\n") + "\n")
+}
+
+// The first number in a line: 7 for both "pub mod day07;" and "(7, Box::new(day07::Day07)),".
+fn day_of(line: &str) -> usize {
+    line.split(|c: char| !c.is_ascii_digit())
+        .find(|s| !s.is_empty())
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+}
+
+// Sets up the given day, with its input at input_path. Refuses to touch a day that's already
+// there; an input that's already there is left alone. Returns the files it created.
+pub fn new_day(day: usize, input_path: &str) -> Result<Vec<String>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("There's no day {} in Advent of Code.", day));
+    }
+    let source = format!("{}/{}.rs", SOURCES, module(day));
+    let lib = format!("{}/lib.rs", SOURCES);
+    if Path::new(&source).exists() {
+        return Err(format!("{} already exists.", source));
+    }
+    let read = |path: &str| fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e));
+    let write =
+        |path: &str, text: &str| fs::write(path, text).map_err(|e| format!("{}: {}", path, e));
+    // Work everything out before writing anything, so nothing's half done if something's off.
+    let code = generate(day, &read(&format!("{}/skel.rs", SOURCES))?)?;
+    let registered = register(day, &read(&lib)?)?;
+    write(&source, &code)?;
+    write(&lib, &registered)?;
+    let mut created = vec![source];
+    let example = format!("{}/{:02}/1", SAMPLES, day);
+    for (path, text) in [(input_path, ""), (example.as_str(), EXAMPLE)].iter() {
+        if Path::new(path).exists() {
+            continue;
+        }
+        if let Some(dir) = Path::new(path).parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        }
+        write(path, text)?;
+        created.push(path.to_string());
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "pub mod bench;
+pub mod day01;
+pub mod day03;
+pub mod examples;
+#[cfg(test)]
+mod skel;
+
+pub fn days() -> Days {
+    vec![
+        (1, Box::new(day01::Day01)),
+        (3, Box::new(day03::Day03)),
+    ]
+}
+";
+
+    #[test]
+    fn test_register() {
+        let lib = register(2, LIB).unwrap();
+        assert!(lib.contains("pub mod day01;\npub mod day02;\npub mod day03;\n"));
+        assert!(lib.contains(
+            "(1, Box::new(day01::Day01)),\n        (2, Box::new(day02::Day02)),\n        (3,"
+        ));
+        let lib = register(12, &lib).unwrap();
+        assert!(lib.contains("pub mod day03;\npub mod day12;\npub mod examples;"));
+        assert!(lib.contains("(12, Box::new(day12::Day12)),\n    ]"));
+        assert!(register(3, &lib).is_err());
+        assert!(register(4, "fn main() {}").is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let skel = include_str!("../../skel.rs");
+        let code = generate(16, skel).unwrap();
+        assert!(code.contains("pub struct Day16;"));
+        assert!(code.contains("impl Solution for Day16 {"));
+        assert!(!code.contains("Skel"));
+        assert!(!code.contains("#[cfg(test)]"));
+        assert!(code.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_example() {
+        let example = aoc::examples::Example::parse("1", EXAMPLE).unwrap();
+        assert!(example.ignore.is_some());
+        assert_eq!(example.input, "");
+    }
+}
@@ -0,0 +1,176 @@
+// Finding cycles in things that step from one state to the next, so we can skip ahead to step
+// fifty billion without simulating all of them.
+//
+// States are compared by a key, which is up to the caller. Often that's the state itself, but it
+// doesn't have to be: day 12's plants keep the same shape, but crawl to the right, so its key
+// leaves out where they are. When the key hides some change like that, we assume the score
+// changes by the same amount in every cycle; see Cycle::score_at.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// A sequence of states that, from step `start` on, repeats every `period` steps.
+#[derive(Clone, Debug)]
+pub struct Cycle<S> {
+    pub start: usize,
+    pub period: usize,
+    // States from step 0 up to start + period, both included. The last one has the same key as
+    // the one at start.
+    states: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    fn new(start: usize, period: usize, states: Vec<S>) -> Cycle<S> {
+        assert_eq!(states.len(), start + period + 1);
+        Cycle {
+            start,
+            period,
+            states,
+        }
+    }
+
+    // Which of the states we've seen stands for given step, and how many full cycles there are
+    // between the two.
+    fn equivalent(&self, step: usize) -> (usize, usize) {
+        if step < self.start {
+            (step, 0)
+        } else {
+            let cycles = (step - self.start) / self.period;
+            (self.start + (step - self.start) % self.period, cycles)
+        }
+    }
+
+    // The state at any step; or rather, a state with the same key.
+    pub fn state_at(&self, step: usize) -> &S {
+        &self.states[self.equivalent(step).0]
+    }
+
+    // How much the score changes over one cycle.
+    pub fn delta<F: Fn(&S) -> i64>(&self, score: F) -> i64 {
+        score(&self.states[self.start + self.period]) - score(&self.states[self.start])
+    }
+
+    // Score at any step, assuming it changes by delta() with every cycle.
+    pub fn score_at<F: Fn(&S) -> i64>(&self, step: usize, score: F) -> i64 {
+        let (i, cycles) = self.equivalent(step);
+        score(&self.states[i]) + cycles as i64 * self.delta(score)
+    }
+}
+
+// Steps through states, remembering every key seen, until one repeats. Gives up (returning None)
+// after `limit` steps. Quick, but keeps all the keys around.
+pub fn find_cycle<S, K, F, G>(initial: S, mut step: F, key: G, limit: usize) -> Option<Cycle<S>>
+where
+    F: FnMut(&S) -> S,
+    G: Fn(&S) -> K,
+    K: Eq + Hash,
+{
+    let mut seen = HashMap::new();
+    seen.insert(key(&initial), 0);
+    let mut states = vec![initial];
+    for n in 1..=limit {
+        let next = step(states.last().unwrap());
+        let previous = seen.insert(key(&next), n);
+        states.push(next);
+        if let Some(start) = previous {
+            return Some(Cycle::new(start, n - start, states));
+        }
+    }
+    None
+}
+
+// Brent's algorithm: finds the same cycle as find_cycle, but only ever keeps two states around
+// while looking for it (and then replays the steps up to the cycle once it's found). Handy when
+// the keys are big. Keys only need comparing, not hashing.
+pub fn find_cycle_brent<S, K, F, G>(
+    initial: S,
+    mut step: F,
+    key: G,
+    limit: usize,
+) -> Option<Cycle<S>>
+where
+    S: Clone,
+    F: FnMut(&S) -> S,
+    G: Fn(&S) -> K,
+    K: PartialEq,
+{
+    // Find the period: the hare runs ahead, and the tortoise teleports to it every power of two
+    // steps, until the hare runs into the tortoise.
+    let mut power = 1;
+    let mut period = 1;
+    let mut steps = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while key(&tortoise) != key(&hare) {
+        if steps >= limit {
+            return None;
+        }
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+        steps += 1;
+    }
+    // Find the start: two states period steps apart walk along until they match.
+    let mut states = vec![initial];
+    for _ in 0..period {
+        let next = step(states.last().unwrap());
+        states.push(next);
+    }
+    let mut start = 0;
+    while key(&states[start]) != key(&states[start + period]) {
+        let next = step(states.last().unwrap());
+        states.push(next);
+        start += 1;
+    }
+    Some(Cycle::new(start, period, states))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 3, 10, 5, 16, 8, 4, 2, 1, 4, 2, 1, ...
+    fn collatz(n: &u64) -> u64 {
+        if n.is_multiple_of(2) {
+            n / 2
+        } else {
+            3 * n + 1
+        }
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        for cycle in [
+            find_cycle(3, collatz, |&n| n, 100).unwrap(),
+            find_cycle_brent(3, collatz, |&n| n, 100).unwrap(),
+        ]
+        .iter()
+        {
+            assert_eq!((cycle.start, cycle.period), (5, 3));
+            assert_eq!(*cycle.state_at(4), 8);
+            assert_eq!(*cycle.state_at(1_000_000_000), 1);
+        }
+        assert!(find_cycle(3, collatz, |&n| n, 7).is_none());
+        assert!(find_cycle(3, collatz, |&n| n, 8).is_some());
+        assert!(find_cycle_brent(1, |&n: &u64| n + 1, |&n| n, 1000).is_none());
+    }
+
+    #[test]
+    fn test_score_at() {
+        // Something crawling right by 2 every step, once it gets going: the key is the shape, the
+        // score is the position.
+        let step = |&(shape, position): &(u8, i64)| (shape.saturating_sub(1), position + 2);
+        let key = |&(shape, _): &(u8, i64)| shape;
+        let score = |&(_, position): &(u8, i64)| position;
+        let cycle = find_cycle((3, 0), step, key, 100).unwrap();
+        assert_eq!((cycle.start, cycle.period), (3, 1));
+        assert_eq!(cycle.delta(score), 2);
+        assert_eq!(cycle.score_at(2, score), 4);
+        assert_eq!(cycle.score_at(50_000_000_000, score), 100_000_000_000);
+        let cycle = find_cycle_brent((3, 0), step, key, 100).unwrap();
+        assert_eq!(cycle.score_at(50_000_000_000, score), 100_000_000_000);
+    }
+}
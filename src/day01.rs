@@ -0,0 +1,117 @@
+use crate::*;
+use std::collections::HashSet;
+
+pub struct Day01;
+
+// Frequency changes to go through before deciding none of the frequencies is ever going to repeat.
+const BUDGET: usize = 10_000_000;
+
+pub fn parse_input(input: &str) -> Result<Vec<i32>, ParseError> {
+    let changes: Vec<i32> = numbered_lines(input)
+        .map(|(n, line)| parse_part(n, line, line.trim(), "a frequency change, like +7 or -3"))
+        .collect::<Result<_, _>>()?;
+    // With no changes at all, part 2 would go round and round without ever getting anywhere.
+    if changes.is_empty() {
+        return Err(ParseError::new(1, 1, "", "at least one frequency change"));
+    }
+    Ok(changes)
+}
+
+// The frequency after a change, unless it's too far out for an i32.
+fn change(frequency: i32, delta: i32) -> Result<i32, Error> {
+    frequency.checked_add(delta).ok_or_else(|| {
+        Error::NoAnswer(format!(
+            "the frequency goes out of range at {} {:+}",
+            frequency, delta
+        ))
+    })
+}
+
+pub fn part1(changes: &[i32]) -> Result<i32, Error> {
+    changes
+        .iter()
+        .try_fold(0, |frequency, &delta| change(frequency, delta))
+}
+
+pub fn part2(changes: &[i32], mut budget: Budget) -> Result<i32, Error> {
+    let mut current_frequency = 0;
+    // Iterate through frequency changes:
+    //  part B: find the frequency which is reached for a second time first, provided we keep
+    //  applying the changes.
+    let mut seen_frequencies: HashSet<i32> = HashSet::new();
+    seen_frequencies.insert(current_frequency);
+    'outer: loop {
+        for delta in changes {
+            budget.spend()?;
+            current_frequency = change(current_frequency, *delta)?;
+            if seen_frequencies.contains(&current_frequency) {
+                break 'outer;
+            } else {
+                seen_frequencies.insert(current_frequency);
+            }
+        }
+    }
+    Ok(current_frequency)
+}
+
+impl Solution for Day01 {
+    type Data = Vec<i32>;
+
+    fn parse(&self, input: &str) -> Result<Self::Data, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, changes: &Self::Data) -> Result<String, Error> {
+        Ok(part1(changes)?.to_string())
+    }
+
+    fn part2(&self, changes: &Self::Data) -> Result<String, Error> {
+        Ok(part2(changes, Budget::unlimited().steps(BUDGET))?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(
+            parse_input("+1\n\n-2"),
+            Err(ParseError::new(
+                2,
+                1,
+                "",
+                "a frequency change, like +7 or -3"
+            ))
+        );
+        assert_eq!(
+            parse_input("+1\n+x\n").unwrap_err().to_string(),
+            "line 2, column 1: expected a frequency change, like +7 or -3, found '+x'"
+        );
+    }
+
+    #[test]
+    fn test_gave_up() {
+        // Always going up, never coming back.
+        assert_eq!(
+            part2(&[1, 2], Budget::unlimited().steps(100)),
+            Err(Error::GaveUp(GaveUp { steps: 100 }))
+        );
+    }
+
+    #[test]
+    fn test_bad_changes() {
+        assert_eq!(
+            parse_input(""),
+            Err(ParseError::new(1, 1, "", "at least one frequency change"))
+        );
+        assert!(parse_input("\n\n").is_err());
+        assert_eq!(part1(&[1, -2, 3]), Ok(2));
+        let overflow = Err(Error::NoAnswer(
+            "the frequency goes out of range at 2147483647 +1".to_string(),
+        ));
+        assert_eq!(part1(&[i32::MAX, 1]), overflow);
+        assert_eq!(part2(&[i32::MAX, 1], Budget::unlimited()), overflow);
+    }
+}
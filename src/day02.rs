@@ -1,11 +1,13 @@
-use aoc::*;
+use crate::*;
 use std::collections::HashMap;
 
-fn parse_input(input: &str) -> Vec<&str> {
-    input.trim().lines().map(|l| l.trim()).collect()
+pub struct Day02;
+
+pub fn parse_input(input: &str) -> Vec<String> {
+    input.trim().lines().map(|l| l.trim().to_string()).collect()
 }
 
-fn part1(box_ids: &[&str]) -> (i32, i32) {
+pub fn part1(box_ids: &[String]) -> (i32, i32) {
     let mut two_repeats = 0;
     let mut three_repeats = 0;
     for id in box_ids.iter() {
@@ -42,7 +44,7 @@ fn contain_prototype_fabric(a: &str, b: &str) -> Option<char> {
     }
 }
 
-fn part2(box_ids: &[&str]) -> Option<String> {
+pub fn part2(box_ids: &[String]) -> Option<String> {
     for (pos, id_a) in box_ids.iter().enumerate() {
         for id_b in box_ids[pos + 1..].iter() {
             match contain_prototype_fabric(id_a, id_b) {
@@ -57,52 +59,19 @@ fn part2(box_ids: &[&str]) -> Option<String> {
     None
 }
 
-fn main() {
-    let input = &read_file("inputs/02");
-    let box_ids = parse_input(input);
-    let (two_repeats, three_repeats) = part1(&box_ids);
-    let checksum = two_repeats * three_repeats;
-    assert_eq!(checksum, 8398);
-    println!("Checksum: {}", checksum);
-
-    let remaining_characters = part2(&box_ids).unwrap();
-    assert_eq!(remaining_characters, "hhvsdkatysmiqjxunezgwcdpr");
-    println!(
-        "Found two boxes containing the suit. Common characters between their ids: {}",
-        remaining_characters
-    );
-}
+impl Solution for Day02 {
+    type Data = Vec<String>;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn parse(&self, input: &str) -> Result<Self::Data, ParseError> {
+        Ok(parse_input(input))
+    }
 
-    #[test]
-    fn test_part1() {
-        let input = r#"
-abcdef
-bababc
-abbcde
-abcccd
-aabcdd
-abcdee
-ababab
-"#;
-        assert_eq!(part1(&parse_input(input)), (4, 3));
+    fn part1(&self, box_ids: &Self::Data) -> Result<String, Error> {
+        let (two_repeats, three_repeats) = part1(box_ids);
+        Ok((two_repeats * three_repeats).to_string())
     }
 
-    #[test]
-    fn test_part2() {
-        let input = r#"
-abcde
-fghij
-klmno
-pqrst
-fguij
-axcye
-wvxyz
-"#;
-        println!("{}", part2(&parse_input(input)).unwrap());
-        assert_eq!(part2(&parse_input(input)).unwrap(), "fgij");
+    fn part2(&self, box_ids: &Self::Data) -> Result<String, Error> {
+        Ok(part2(box_ids).unwrap())
     }
 }
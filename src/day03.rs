@@ -0,0 +1,102 @@
+use crate::*;
+use std::collections::HashSet;
+
+pub struct Day03;
+
+#[derive(Debug)]
+pub struct Rectangle {
+    id: usize,
+    x: usize,
+    y: usize,
+    w: usize,
+    h: usize,
+}
+
+const FABRIC_SIZE: usize = 1000;
+
+pub fn parse_input(input: &str) -> Result<Vec<Rectangle>, ParseError> {
+    let mut rectangles: Vec<Rectangle> = Vec::new();
+    for (n, line) in numbered_lines(input) {
+        let [id, x, y, w, h] = exact_integers(n, line, "a claim, like #1 @ 1,3: 4x4")?;
+        let r = Rectangle { id, x, y, w, h };
+        if r.x + r.w > FABRIC_SIZE || r.y + r.h > FABRIC_SIZE {
+            return Err(ParseError::at_line(
+                n,
+                line,
+                "a claim that fits on the fabric",
+            ));
+        }
+        rectangles.push(r);
+    }
+    Ok(rectangles)
+}
+
+pub fn part1(rectangles: &[Rectangle]) -> usize {
+    // Paint the fabric; add 1 for every rectangle covering given square.
+    let mut fabric = Grid::new(FABRIC_SIZE, FABRIC_SIZE, 0usize);
+    for r in rectangles {
+        for (_, element) in fabric.region_mut(Point::new(r.x, r.y), r.w, r.h) {
+            *element += 1;
+        }
+    }
+    fabric.iter().filter(|&(_, &x)| x > 1).count()
+}
+
+pub fn part2(rectangles: &[Rectangle]) -> usize {
+    // Paint the fabric with rectangle ids. Just like with normal paint, only last (topmost) id is
+    // visible. If we're painting over an existing id, mark both old rectangle id and current one
+    // as tainted.
+    let mut fabric = Grid::new(FABRIC_SIZE, FABRIC_SIZE, 0usize);
+    let mut tainted: HashSet<usize> = HashSet::new();
+    for r in rectangles {
+        let mut current_is_tainted = false;
+        for (_, element) in fabric.region_mut(Point::new(r.x, r.y), r.w, r.h) {
+            if *element > 0 {
+                tainted.insert(*element);
+                current_is_tainted = true;
+            }
+            *element = r.id;
+        }
+        if current_is_tainted {
+            tainted.insert(r.id);
+        }
+    }
+    let all_ids: HashSet<usize> = rectangles.iter().map(|r| r.id).collect();
+    let viable: Vec<usize> = all_ids.difference(&tainted).cloned().collect();
+    assert_eq!(viable.len(), 1);
+    viable[0]
+}
+
+impl Solution for Day03 {
+    type Data = Vec<Rectangle>;
+
+    fn parse(&self, input: &str) -> Result<Self::Data, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, rectangles: &Self::Data) -> Result<String, Error> {
+        Ok(part1(rectangles).to_string())
+    }
+
+    fn part2(&self, rectangles: &Self::Data) -> Result<String, Error> {
+        Ok(part2(rectangles).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(
+            parse_input("#1 @ 1,3: 4x4\n#2 @ 3,1: 4\n").unwrap_err(),
+            ParseError::new(2, 1, "#2 @ 3,1: 4", "a claim, like #1 @ 1,3: 4x4")
+        );
+        assert_eq!(
+            parse_input("#1 @ 1,99999999999999999999: 4x4").unwrap_err(),
+            ParseError::new(1, 8, "99999999999999999999", "a number that fits in usize")
+        );
+        assert!(parse_input("#1 @ 999,3: 4x4").is_err());
+    }
+}
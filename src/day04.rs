@@ -1,60 +1,85 @@
-use aoc::*;
+use crate::*;
 use regex::Regex;
 use std::collections::HashMap;
 
-struct Schedule<'a> {
+pub struct Day04;
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Schedule {
     // A structure mapping from a guard to his list of sleep schedules per day.
     //                         guard_id->     day->    sleep_schedule
-    sleeping_patterns: HashMap<usize, HashMap<&'a str, Vec<usize>>>,
+    sleeping_patterns: HashMap<usize, HashMap<String, Vec<usize>>>,
 }
 
-fn parse_input(input: &str) -> Schedule {
+pub fn parse_input(input: &str) -> Result<Schedule, ParseError> {
     // Read in all data.
     let log_re =
         Regex::new(r"\[\d\d\d\d-(?P<date>\d\d-\d\d) (?P<hour>\d\d):(?P<minute>\d\d)] (?P<text>.+)")
             .unwrap();
-    let mut logs: Vec<&str> = input.trim().lines().collect();
-    logs.sort();
+    // Keep the line numbers around, as sorting shuffles the lines.
+    let mut logs: Vec<(usize, &str)> = numbered_lines(input).collect();
+    logs.sort_by_key(|&(_, line)| line);
     let mut current_guard: usize = 0;
     let mut sleeping_patterns = HashMap::new();
     // I'm assuming the data is well formed, that is:
     // - no unpaired wake/sleep
-    // - no wake before sleep
     // - midnight hour begins and finish awake
-    for log_line in logs {
-        let caps = log_re.captures(log_line).unwrap();
+    for (n, log_line) in logs {
+        let caps = log_re.captures(log_line).ok_or_else(|| {
+            ParseError::at_line(
+                n,
+                log_line,
+                "a log entry, like [1518-11-01 00:05] falls asleep",
+            )
+        })?;
         let date = caps.name("date").unwrap().as_str();
-        let minute: usize = caps.name("minute").unwrap().as_str().parse().unwrap();
+        let minute_text = caps.name("minute").unwrap().as_str();
+        let minute: usize = parse_part(n, log_line, minute_text, "a minute")?;
         let entry = caps.name("text").unwrap().as_str();
         if entry.ends_with("begins shift") {
-            current_guard = entry
-                .split_whitespace()
-                .nth(1)
-                .unwrap()
-                .trim_start_matches('#')
-                .parse()
-                .unwrap();
+            let guard = entry.split_whitespace().nth(1).unwrap_or(entry);
+            current_guard = parse_part(
+                n,
+                log_line,
+                guard.trim_start_matches('#'),
+                "a guard number, like #10",
+            )?;
             continue;
         }
         let dates = &mut sleeping_patterns
             .entry(current_guard)
             .or_insert_with(HashMap::new);
-        let day = dates.entry(date).or_insert_with(Vec::new);
+        let day = dates.entry(date.to_string()).or_insert_with(Vec::new);
+        if minute < day.len() {
+            return Err(ParseError::at_part(
+                n,
+                log_line,
+                minute_text,
+                "a minute later than the guard's previous entry",
+            ));
+        }
         if entry.ends_with("falls asleep") {
             let mut awake_period = vec![0usize; minute - day.len()];
             day.append(&mut awake_period);
             day.push(1);
-        }
-        if entry.ends_with("wakes up") {
+        } else if entry.ends_with("wakes up") {
             let mut asleep_period = vec![1usize; minute - day.len()];
             day.append(&mut asleep_period);
             day.push(0);
+        } else {
+            return Err(ParseError::at_part(
+                n,
+                log_line,
+                entry,
+                "'begins shift', 'falls asleep' or 'wakes up'",
+            ));
         }
     }
-    Schedule { sleeping_patterns }
+    Ok(Schedule { sleeping_patterns })
 }
 
-fn part1(schedule: &Schedule) -> usize {
+pub fn part1(schedule: &Schedule) -> usize {
     // Find the biggest sleeper
     let mut total_sleep: HashMap<usize, usize> = HashMap::new();
     // This can be converted to a chain of functional calls for sure - but I'm tired.
@@ -80,7 +105,7 @@ fn part1(schedule: &Schedule) -> usize {
     sleepiest_guard * favourite_minute
 }
 
-fn part2(schedule: &Schedule) -> usize {
+pub fn part2(schedule: &Schedule) -> usize {
     // Find the biggest sleeper
     // guard_id -> number_of_times_slept_in_particular_minute
     let mut minutes_frequency: HashMap<usize, Vec<usize>> = HashMap::new();
@@ -112,51 +137,41 @@ fn part2(schedule: &Schedule) -> usize {
     chosen_one.0 * chosen_one.1
 }
 
-fn main() {
-    let input = read_file("inputs/04");
-    let schedule = parse_input(&input);
-    let answer1 = part1(&schedule);
-    assert_eq!(answer1, 84636);
-    println!("Part 1: {}", answer1);
+impl Solution for Day04 {
+    type Data = Schedule;
+
+    fn parse(&self, input: &str) -> Result<Self::Data, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, schedule: &Self::Data) -> Result<String, Error> {
+        Ok(part1(schedule).to_string())
+    }
 
-    let answer2 = part2(&schedule);
-    assert_eq!(answer2, 91679);
-    println!("Part 2: {}", answer2);
+    fn part2(&self, schedule: &Self::Data) -> Result<String, Error> {
+        Ok(part2(schedule).to_string())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = r#"
-[1518-11-01 00:00] Guard #10 begins shift
-[1518-11-01 00:05] falls asleep
-[1518-11-01 00:25] wakes up
-[1518-11-01 00:30] falls asleep
-[1518-11-01 00:55] wakes up
-[1518-11-01 23:58] Guard #99 begins shift
-[1518-11-02 00:40] falls asleep
-[1518-11-02 00:50] wakes up
-[1518-11-03 00:05] Guard #10 begins shift
-[1518-11-03 00:24] falls asleep
-[1518-11-03 00:29] wakes up
-[1518-11-04 00:02] Guard #99 begins shift
-[1518-11-04 00:36] falls asleep
-[1518-11-04 00:46] wakes up
-[1518-11-05 00:03] Guard #99 begins shift
-[1518-11-05 00:45] falls asleep
-[1518-11-05 00:55] wakes up
-"#;
-
-    #[test]
-    fn test_part1() {
-        let steps = parse_input(INPUT);
-        assert_eq!(part1(&steps), 240);
-    }
-
     #[test]
-    fn test_part2() {
-        let steps = parse_input(INPUT);
-        assert_eq!(part2(&steps), 4455);
+    fn test_parse_input() {
+        assert_eq!(
+            parse_input("[1518-11-01 00:00] Guard #1O begins shift").unwrap_err(),
+            ParseError::new(1, 27, "1O", "a guard number, like #10")
+        );
+        assert_eq!(
+            parse_input("[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] snores")
+                .unwrap_err(),
+            ParseError::new(
+                2,
+                20,
+                "snores",
+                "'begins shift', 'falls asleep' or 'wakes up'"
+            )
+        );
     }
 }
@@ -1,11 +1,13 @@
-use aoc::*;
+use crate::*;
 use std::collections::HashMap;
 
-fn parse_input(input: &str) -> Vec<char> {
+pub struct Day05;
+
+pub fn parse_input(input: &str) -> Vec<char> {
     input.trim().chars().collect()
 }
 
-fn part1(polymer: &[char]) -> usize {
+pub fn part1(polymer: &[char]) -> usize {
     // Go through the polymer left to right, check characters, add them to final_polymer
     // when appropriate.
     let mut final_polymer: Vec<char> = Vec::new();
@@ -14,7 +16,7 @@ fn part1(polymer: &[char]) -> usize {
         // current unit.
         if final_polymer
             .last()
-            .map(|&previous| previous.eq_ignore_ascii_case(&unit) && previous != *unit)
+            .map(|&previous| previous.eq_ignore_ascii_case(unit) && previous != *unit)
             .unwrap_or(false)
         {
             final_polymer.pop();
@@ -25,7 +27,7 @@ fn part1(polymer: &[char]) -> usize {
     final_polymer.len()
 }
 
-fn part2(polymer: &[char]) -> usize {
+pub fn part2(polymer: &[char]) -> usize {
     let all_units: Vec<char> = "abcdefghijklmnopqrstuvwxyz".chars().collect();
     let mut efficiency: HashMap<char, usize> = HashMap::new();
     for removed_unit in all_units {
@@ -59,39 +61,18 @@ fn part2(polymer: &[char]) -> usize {
     *efficiency.values().min().unwrap()
 }
 
-fn main() {
-    let input = read_file("inputs/05");
-    println!("Welcome to Aperture Science Polymer Reaction, Scanning and Optimisation Chamber!");
-    println!(
-        "Processing a polymer with starting length of {} units.",
-        input.len()
-    );
-    let polymer = parse_input(&input);
-    let final_polymer_length = part1(&polymer);
-    assert_eq!(final_polymer_length, 10878);
-    println!("Final polymer is {} units long", final_polymer_length);
-
-    let shortest_polymer_length = part2(&polymer);
-    assert_eq!(shortest_polymer_length, 6874);
-    println!(
-        "Shortest polymer is {} units long.",
-        shortest_polymer_length
-    );
-}
+impl Solution for Day05 {
+    type Data = Vec<char>;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const INPUT: &str = r#"dabAcCaCBAcCcaDA"#;
+    fn parse(&self, input: &str) -> Result<Self::Data, ParseError> {
+        Ok(parse_input(input))
+    }
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(&parse_input(INPUT)), 10);
+    fn part1(&self, polymer: &Self::Data) -> Result<String, Error> {
+        Ok(part1(polymer).to_string())
     }
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(&parse_input(INPUT)), 4);
+    fn part2(&self, polymer: &Self::Data) -> Result<String, Error> {
+        Ok(part2(polymer).to_string())
     }
 }
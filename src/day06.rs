@@ -1,11 +1,27 @@
-use aoc::*;
+use crate::*;
 use std::str::FromStr;
 
-fn parse_input(input: &str) -> Vec<Point> {
-    input.trim().lines().map(|s| Point::from_str(s).unwrap()).collect()
+pub struct Day06;
+
+// Maximum total distance to all seeds, for a point to be considered safe. The example uses a
+// smaller one; it's the safe_area_within parameter.
+const SAFE_AREA_WITHIN: usize = 10_000;
+
+pub struct Map {
+    seeds: Vec<Point>,
+    safe_area_within: usize,
 }
 
-fn part_ab(seeds: &[Point], safe_area_within: usize) -> (usize, usize) {
+pub fn parse_input(input: &str) -> Result<Vec<Point>, ParseError> {
+    numbered_lines(input)
+        .map(|(n, line)| {
+            Point::from_str(line)
+                .map_err(|_| ParseError::at_line(n, line, "coordinates, like 1, 6"))
+        })
+        .collect()
+}
+
+pub fn part_ab(seeds: &[Point], safe_area_within: usize) -> (usize, usize) {
     // Go through entire square piece of the map; for every point calculate distance to all seed
     // points and:
     // * for part A: pick closest seed, score one more point for that seed.
@@ -27,7 +43,7 @@ fn part_ab(seeds: &[Point], safe_area_within: usize) -> (usize, usize) {
                 p_distances.push((p.distance_to(seed), n));
             }
             // part B: sum all distances, check if the sum is under desired threshold.
-            let p_total_distance:usize = p_distances.iter().map(|x| x.0).sum();
+            let p_total_distance: usize = p_distances.iter().map(|x| x.0).sum();
             if p_total_distance < safe_area_within {
                 safe_area += 1;
             }
@@ -50,39 +66,38 @@ fn part_ab(seeds: &[Point], safe_area_within: usize) -> (usize, usize) {
             // that seed/
             areas[closest_seed] = match areas[closest_seed] {
                 None => Some(1usize),
-                Some(x) => Some(x+1),
+                Some(x) => Some(x + 1),
             }
         }
     }
-    let largest_size = areas.into_iter().filter_map(|x| x).max().unwrap();
+    let largest_size = areas.into_iter().flatten().max().unwrap();
     (largest_size, safe_area)
 }
 
-fn main() {
-    let seeds = parse_input(&read_file("inputs/06"));
-    let (largest_area_size, safe_area_size) = part_ab(&seeds, 10_000);
-    assert_eq!(largest_area_size, 3894);
-    println!("Largest area (except infinite ones) is {}", largest_area_size);
-    assert_eq!(safe_area_size, 39398);
-    println!("Safe area size: {}", safe_area_size);
-}
+impl Solution for Day06 {
+    type Data = Map;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn parse(&self, input: &str) -> Result<Self::Data, ParseError> {
+        Ok(Map {
+            seeds: parse_input(input)?,
+            safe_area_within: SAFE_AREA_WITHIN,
+        })
+    }
+
+    const PARAMS: &'static [&'static str] = &["safe_area_within"];
 
-    const INPUT: &str = r#"
-1, 1
-1, 6
-8, 3
-3, 4
-5, 5
-8, 9
-"#;
+    fn parse_with(&self, input: &str, params: &Params) -> Result<Self::Data, Error> {
+        Ok(Map {
+            safe_area_within: params.get("safe_area_within", SAFE_AREA_WITHIN)?,
+            ..self.parse(input)?
+        })
+    }
+
+    fn part1(&self, map: &Self::Data) -> Result<String, Error> {
+        Ok(part_ab(&map.seeds, map.safe_area_within).0.to_string())
+    }
 
-    #[test]
-    fn test_part1() {
-        let seeds = parse_input(INPUT);
-        assert_eq!(part_ab(&seeds, 32), (17, 16));
+    fn part2(&self, map: &Self::Data) -> Result<String, Error> {
+        Ok(part_ab(&map.seeds, map.safe_area_within).1.to_string())
     }
 }
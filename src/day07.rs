@@ -1,10 +1,24 @@
-use aoc::*;
+use crate::*;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
+pub struct Day07;
+
+// The example has fewer workers and cheaper steps; those are the workers and cost parameters.
+const NUMBER_OF_WORKERS: i32 = 5;
+const STATIC_WORK_COST: i32 = 60;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Instructions {
+    steps: Vec<Step>,
+    workers: i32,
+    cost: i32,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
-struct Step {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Step {
     name: char,
     prerequisites: HashSet<char>,
 }
@@ -12,6 +26,8 @@ struct Step {
 // Well, what did you expect? :D
 #[derive(Clone, Debug)]
 struct SantaLittleHelper {
+    // Only there to tell the elves apart in debug printouts.
+    #[allow(dead_code)]
     id: i32,
     last_worked_on: char,
     work_left: i32,
@@ -59,7 +75,16 @@ impl PartialOrd for Step {
     }
 }
 
-fn parse_input(input: &str) -> Vec<Step> {
+// Step names are single capital letters; work cost depends on the letter.
+fn step_name(n: usize, line: &str, word: &str) -> Result<char, ParseError> {
+    let mut chars = word.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_uppercase() => Ok(c),
+        _ => Err(ParseError::at_part(n, line, word, "a step name, A to Z")),
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Step>, ParseError> {
     // Record names of steps we've seen in the input; we'll use it to identify possible starting
     // point.
     let mut seen_targets = HashSet::new();
@@ -69,10 +94,17 @@ fn parse_input(input: &str) -> Vec<Step> {
     // We use a hash here during input reading for convenience, as it's faster to check if step is
     // already there.
     let mut steps = HashMap::new();
-    for line in input.trim().lines() {
+    for (n, line) in numbered_lines(input) {
         let words: Vec<&str> = line.split_whitespace().collect();
-        let target_step_name = words[7].chars().nth(0).unwrap();
-        let prerequisite_step_name = words[1].chars().nth(0).unwrap();
+        if words.len() != 10 {
+            return Err(ParseError::at_line(
+                n,
+                line,
+                "an instruction, like 'Step C must be finished before step A can begin.'",
+            ));
+        }
+        let target_step_name = step_name(n, line, words[7])?;
+        let prerequisite_step_name = step_name(n, line, words[1])?;
         let target_step = steps
             .entry(target_step_name)
             .or_insert_with(|| Step::new(target_step_name));
@@ -89,10 +121,10 @@ fn parse_input(input: &str) -> Vec<Step> {
     // With all steps gathered, we can turn steps hash into a vector.
     let mut steps: Vec<Step> = steps.drain().map(|(_, v)| v).collect();
     steps.sort();
-    steps
+    Ok(steps)
 }
 
-fn part1(steps: &[Step]) -> String {
+pub fn part1(steps: &[Step]) -> String {
     // Time to work out the sequence of steps.
     let mut steps = steps.to_owned();
     let mut step_sequence = String::with_capacity(steps.len());
@@ -110,7 +142,7 @@ fn part1(steps: &[Step]) -> String {
     step_sequence
 }
 
-fn part2(steps: &[Step], total_workers: i32, fixed_cost: i32) -> i32 {
+pub fn part2(steps: &[Step], total_workers: i32, fixed_cost: i32) -> i32 {
     // Elves, assemble!
     // steps will keep outstanding steps to be done.
     let mut steps = steps.to_owned();
@@ -180,49 +212,106 @@ fn part2(steps: &[Step], total_workers: i32, fixed_cost: i32) -> i32 {
     clock - 1
 }
 
-fn main() {
-    let number_of_workers = 5;
-    let static_work_cost = 60;
-    let steps = parse_input(&read_file("inputs/07"));
-
-    let step_sequence = part1(&steps);
-    assert_eq!(step_sequence, "GRTAHKLQVYWXMUBCZPIJFEDNSO");
-    println!(
-        "Here's the sequence of steps for a single worker: {}",
-        step_sequence
-    );
-
-    let time_elapsed = part2(&steps, number_of_workers, static_work_cost);
-    assert_eq!(time_elapsed, 1115);
-    println!(
-        "We're done with construction; it only took us {} seconds.",
-        time_elapsed
-    );
+impl Solution for Day07 {
+    type Data = Instructions;
+
+    fn parse(&self, input: &str) -> Result<Self::Data, ParseError> {
+        Ok(Instructions {
+            steps: parse_input(input)?,
+            workers: NUMBER_OF_WORKERS,
+            cost: STATIC_WORK_COST,
+        })
+    }
+
+    const PARAMS: &'static [&'static str] = &["workers", "cost"];
+
+    // Without a worker, nothing ever gets done; and work can't take negative time.
+    fn parse_with(&self, input: &str, params: &Params) -> Result<Self::Data, Error> {
+        let workers = params.get("workers", NUMBER_OF_WORKERS)?;
+        if workers < 1 {
+            return Err(Error::Param(format!(
+                "parameter workers can't be '{}', there has to be at least one",
+                workers
+            )));
+        }
+        let cost = params.get("cost", STATIC_WORK_COST)?;
+        if cost < 0 {
+            return Err(Error::Param(format!(
+                "parameter cost can't be '{}', it has to be 0 or more",
+                cost
+            )));
+        }
+        Ok(Instructions {
+            workers,
+            cost,
+            ..self.parse(input)?
+        })
+    }
+
+    fn part1(&self, instructions: &Self::Data) -> Result<String, Error> {
+        Ok(part1(&instructions.steps))
+    }
+
+    fn part2(&self, instructions: &Self::Data) -> Result<String, Error> {
+        let Instructions {
+            steps,
+            workers,
+            cost,
+        } = instructions;
+        Ok(part2(steps, *workers, *cost).to_string())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = r#"
-Step C must be finished before step A can begin.
-Step C must be finished before step F can begin.
-Step A must be finished before step B can begin.
-Step A must be finished before step D can begin.
-Step B must be finished before step E can begin.
-Step D must be finished before step E can begin.
-Step F must be finished before step E can begin.
-"#;
-
     #[test]
-    fn test_part1() {
-        let steps = parse_input(INPUT);
-        assert_eq!(part1(&steps), "CABDFE");
+    fn test_parse_input() {
+        assert_eq!(
+            parse_input("Step C must be finished before step a can begin.").unwrap_err(),
+            ParseError::new(1, 37, "a", "a step name, A to Z")
+        );
+        assert_eq!(
+            parse_input("Step C must be finished before step A.")
+                .unwrap_err()
+                .line,
+            1
+        );
     }
 
     #[test]
-    fn test_part2() {
-        let steps = parse_input(INPUT);
-        assert_eq!(part2(&steps, 2, 0), 15);
+    fn test_params() {
+        let input = "Step C must be finished before step A can begin.";
+        let with = |name: &str, value: &str| {
+            let mut params = Params::default();
+            params.set(name, value);
+            Day07
+                .parse_with(input, &params)
+                .map(|i| (i.workers, i.cost))
+        };
+        assert_eq!(with("workers", "2"), Ok((2, STATIC_WORK_COST)));
+        assert_eq!(
+            with("workers", "0"),
+            Err(Error::Param(
+                "parameter workers can't be '0', there has to be at least one".to_string()
+            ))
+        );
+        assert_eq!(with("cost", "0"), Ok((NUMBER_OF_WORKERS, 0)));
+        assert_eq!(
+            with("cost", "-100"),
+            Err(Error::Param(
+                "parameter cost can't be '-100', it has to be 0 or more".to_string()
+            ))
+        );
+        // A typo doesn't quietly get the default.
+        let mut params = Params::default();
+        params.set("worker", "2");
+        assert_eq!(
+            Day07.run_with(input, None, &params).map(|_| ()),
+            Err(Error::Param(
+                "there's no parameter worker, this day takes workers, cost".to_string()
+            ))
+        );
     }
 }
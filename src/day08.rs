@@ -1,8 +1,13 @@
-use aoc::*;
+use crate::*;
+
+pub struct Day08;
 
 // Tree elements.
 #[derive(Debug)]
-struct Node {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Node {
+    // Only used in debug printouts.
+    #[allow(dead_code)]
     index: usize,
     children: Vec<usize>,
     metadata: Vec<usize>,
@@ -39,16 +44,28 @@ enum Expectation {
     EvaluateNode(usize),
 }
 
-fn parse_input(input: &str) -> Vec<Node> {
-    let mut numbers: Vec<usize> = input
-        .trim()
-        .split_whitespace()
-        .map(|n| n.parse().unwrap())
-        .collect();
+// A number from the input, together with where it came from: (value, line number, line, text).
+type Number<'a> = (usize, usize, &'a str, &'a str);
+
+// Takes next number off the input, complaining if there's none left.
+fn next_number(numbers: &mut Vec<Number>, end: &ParseError) -> Result<usize, ParseError> {
+    numbers.pop().map(|n| n.0).ok_or_else(|| end.clone())
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Node>, ParseError> {
+    let mut numbers: Vec<Number> = vec![];
+    for (n, line) in numbered_lines(input) {
+        for word in line.split_whitespace() {
+            numbers.push((parse_part(n, line, word, "a number")?, n, line, word));
+        }
+    }
+    // Running out of numbers before the tree is complete gets blamed on the end of input.
+    let end = match numbers.last() {
+        Some(&(_, n, line, _)) => ParseError::new(n, line.chars().count() + 1, "", "more numbers"),
+        None => ParseError::new(1, 1, "", "a tree"),
+    };
     // Just for convenience, so I can use push/pop.
     numbers.reverse();
-    // Minimal tree is one node, (0, 0).
-    assert!(numbers.len() >= 2);
     // Nodes storage. This is our tree, just serialized in a vector.
     let mut nodes: Vec<Node> = Vec::with_capacity(numbers.len() / 2);
     // Parser stack. We'll push to it things we expect to happen next.
@@ -62,8 +79,8 @@ fn parse_input(input: &str) -> Vec<Node> {
             // We're expecting data for a node[i]. It should already have been allocataed by its
             // parent.
             Expectation::NodeElement(i) => {
-                let kid_count = numbers.pop().unwrap() as usize;
-                let metadata_count = numbers.pop().unwrap() as usize;
+                let kid_count = next_number(&mut numbers, &end)?;
+                let metadata_count = next_number(&mut numbers, &end)?;
                 // Update metadata count expectations in the Node.
                 nodes[i].expected_metadata_count = metadata_count;
                 // Push an evaluation request for this node. It'll complete once we have all child
@@ -89,7 +106,7 @@ fn parse_input(input: &str) -> Vec<Node> {
             }
             // We're expecting metadata element for node[i].
             Expectation::MetadataElement(i) => {
-                let metadata_entry = numbers.pop().unwrap();
+                let metadata_entry = next_number(&mut numbers, &end)?;
                 nodes[i].metadata.push(metadata_entry);
             }
             // We're expecting to calculate value of node[i].
@@ -124,51 +141,57 @@ fn parse_input(input: &str) -> Vec<Node> {
         }
     }
     // At this point tree should be complete, and all input should have been consumed.
-    assert!(numbers.is_empty());
+    if let Some(&(_, n, line, word)) = numbers.last() {
+        return Err(ParseError::at_part(n, line, word, "end of input"));
+    }
     // All Nodes should have gotten as many metadata entries as they initially expected.
     nodes.iter().for_each(|n| n.verify_metadata());
     // assert!(false);
-    nodes
+    Ok(nodes)
 }
 
-fn part1(nodes: &[Node]) -> usize {
+pub fn part1(nodes: &[Node]) -> usize {
     // FIXME: why do I need a type hint for that first sum? metadata is typed, isn't that enough?
     nodes.iter().map(|n| n.metadata.iter().sum::<usize>()).sum()
 }
 
-fn part2(nodes: &[Node]) -> usize {
+pub fn part2(nodes: &[Node]) -> usize {
     nodes[0].value.unwrap()
 }
 
-fn main() {
-    let nodes = parse_input(&read_file("inputs/08"));
+impl Solution for Day08 {
+    type Data = Vec<Node>;
 
-    let answer1 = part1(&nodes);
-    assert_eq!(answer1, 40746);
-    println!("Sum of all metadata entries: {}", answer1);
+    fn parse(&self, input: &str) -> Result<Self::Data, ParseError> {
+        parse_input(input)
+    }
 
-    let answer2 = part2(&nodes);
-    assert_eq!(answer2, 37453);
-    println!("Value of the root node: {}", answer2);
+    fn part1(&self, nodes: &Self::Data) -> Result<String, Error> {
+        Ok(part1(nodes).to_string())
+    }
+
+    fn part2(&self, nodes: &Self::Data) -> Result<String, Error> {
+        Ok(part2(nodes).to_string())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = r#"
-2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2
-"#;
-
-    #[test]
-    fn test_part1() {
-        let nodes = parse_input(INPUT);
-        assert_eq!(part1(&nodes), 138);
-    }
-
     #[test]
-    fn test_part2() {
-        let nodes = parse_input(INPUT);
-        assert_eq!(part2(&nodes), 66);
+    fn test_parse_input() {
+        assert_eq!(
+            parse_input("2 3 0 3 10 11 12 1 1 0 1 99 2 1 1").unwrap_err(),
+            ParseError::new(1, 34, "", "more numbers")
+        );
+        assert_eq!(
+            parse_input("0 1 99\n7").unwrap_err(),
+            ParseError::new(2, 1, "7", "end of input")
+        );
+        assert_eq!(
+            parse_input("0 1 x").unwrap_err(),
+            ParseError::new(1, 5, "x", "a number")
+        );
     }
 }
@@ -1,12 +1,14 @@
-use aoc::*;
+use crate::simulation::{self, Simulation};
+use crate::*;
 use std::collections::VecDeque;
 
-const PUZZLE_INPUT: &str = "473 players; last marble is worth 70904 points";
+pub struct Day09;
+
 const LUCKY_NUMBER: usize = 23;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Hash)]
 // We will use this struct to keep the game state.
-struct Game {
+pub struct Game {
     player_count: usize,
     game_length: usize,
     turn: usize,
@@ -19,7 +21,7 @@ struct Game {
 }
 
 impl Game {
-    fn new(player_count: usize, game_length: usize) -> Game {
+    pub fn new(player_count: usize, game_length: usize) -> Game {
         let mut g = Game {
             player_count,
             game_length,
@@ -52,7 +54,7 @@ impl Game {
         self.turn += 1;
         // Newly placed ball has the same number on its face as the turn number.
         let new_ball = self.turn;
-        if new_ball % LUCKY_NUMBER == 0 {
+        if new_ball.is_multiple_of(LUCKY_NUMBER) {
             // Ball being placed is a multiple of LUCKY_NUMBER!
             // Move current 7 positions counterclockwise, remove it.
             self.rotate_ccw(7);
@@ -67,37 +69,58 @@ impl Game {
     }
 
     // https://youtu.be/6_5O-nUiZ_0 :3
-    fn play_the(&mut self) -> usize {
-        let stopwatch = Stopwatch::start();
-        for _ in 1..=self.game_length {
-            self.place_next_ball();
-        }
-        stopwatch.split();
+    pub fn play_the(&mut self) -> usize {
+        simulation::run(self);
         *self.scores.iter().max().unwrap()
     }
 }
 
-fn parse_input(input: &str) -> Game {
-    let words: Vec<&str> = input.split_whitespace().collect();
-    Game::new(
-        words[0].parse::<usize>().unwrap(),
-        words[6].parse::<usize>().unwrap(),
-    )
+impl Simulation for Game {
+    fn step(&mut self) {
+        self.place_next_ball();
+    }
+
+    fn is_done(&self) -> bool {
+        self.turn >= self.game_length
+    }
+
+    fn state_hash(&self) -> u64 {
+        simulation::state_hash(self)
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Game, ParseError> {
+    let (n, line) = numbered_lines(input)
+        .next()
+        .ok_or_else(|| ParseError::new(1, 1, "", "a game description"))?;
+    let [player_count, last_marble] = exact_integers(
+        n,
+        line,
+        "a game description, like '10 players; last marble is worth 1618 points'",
+    )?;
+    if player_count == 0 {
+        return Err(ParseError::at_line(n, line, "at least one player"));
+    }
+    Ok(Game::new(player_count, last_marble))
 }
 
-fn main() {
-    let mut game = parse_input(&PUZZLE_INPUT);
-    let highscore = game.clone().play_the();
-    assert_eq!(highscore, 371_284);
-    println!("Winning Elf's high score: {}", highscore);
-
-    game.game_length *= 100;
-    let highscore = game.play_the();
-    assert_eq!(highscore, 3_038_972_494);
-    println!(
-        "Winning Elf's high score when we're playing for much longer: {}",
-        highscore
-    );
+impl Solution for Day09 {
+    type Data = Game;
+
+    fn parse(&self, input: &str) -> Result<Self::Data, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, game: &Self::Data) -> Result<String, Error> {
+        Ok(game.clone().play_the().to_string())
+    }
+
+    // What if the game went on for much longer?
+    fn part2(&self, game: &Self::Data) -> Result<String, Error> {
+        let mut game = game.clone();
+        game.game_length *= 100;
+        Ok(game.play_the().to_string())
+    }
 }
 
 #[cfg(test)]
@@ -105,30 +128,23 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part1() {
-        assert_eq!(
-            parse_input("9 players; last marble is worth 25 points").play_the(),
-            32,
-        );
-        assert_eq!(
-            parse_input("10 players; last marble is worth 1618 points").play_the(),
-            8317,
-        );
-        assert_eq!(
-            parse_input("13 players; last marble is worth 7999 points").play_the(),
-            146373,
-        );
-        assert_eq!(
-            parse_input("17 players; last marble is worth 1104 points").play_the(),
-            2764,
-        );
+    fn test_parse_input() {
         assert_eq!(
-            parse_input("21 players; last marble is worth 6111 points").play_the(),
-            54718,
+            parse_input("0 players; last marble is worth 25 points").unwrap_err(),
+            ParseError::at_line(
+                1,
+                "0 players; last marble is worth 25 points",
+                "at least one player"
+            )
         );
+        let line = "9 players; last marble is worth many points";
         assert_eq!(
-            parse_input("30 players; last marble is worth 5807 points").play_the(),
-            37305,
+            parse_input(line).unwrap_err(),
+            ParseError::at_line(
+                1,
+                line,
+                "a game description, like '10 players; last marble is worth 1618 points'"
+            )
         );
     }
 }
@@ -0,0 +1,185 @@
+#[cfg(feature = "render")]
+use crate::render::Renderer;
+use crate::render::{self, Frame, Render};
+use crate::simulation::{self, Simulation};
+use crate::*;
+
+pub struct Day10;
+
+#[derive(Clone, Copy, Debug, Hash)]
+pub struct Star {
+    position: Point2,
+    velocity: Point2,
+}
+
+// My god, it's full of stars!
+#[derive(Clone, Debug)]
+pub struct Starfield {
+    stars: Vec<Star>,
+}
+
+impl Starfield {
+    // Top left and bottom right corners of the smallest rectangle holding all the stars.
+    pub fn bounding_box(&self) -> (Point2, Point2) {
+        let first = self.stars[0].position;
+        self.stars.iter().fold((first, first), |(min, max), s| {
+            (min.min_each(&s.position), max.max_each(&s.position))
+        })
+    }
+
+    pub fn area(&self) -> i64 {
+        let (min, max) = self.bounding_box();
+        let size = max - min;
+        size.x * size.y
+    }
+
+    // Undoes a step, for when we've gone one too far.
+    pub fn step_back(&mut self) {
+        for star in self.stars.iter_mut() {
+            star.position -= star.velocity;
+        }
+    }
+}
+
+impl Simulation for Starfield {
+    fn step(&mut self) {
+        for star in self.stars.iter_mut() {
+            star.position += star.velocity;
+        }
+    }
+
+    fn state_hash(&self) -> u64 {
+        simulation::state_hash(&self.stars)
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Starfield, ParseError> {
+    let mut stars = vec![];
+    for (n, line) in numbered_lines(input) {
+        let [x, y, dx, dy] =
+            exact_integers(n, line, "a star, like position=< 9,  1> velocity=< 0,  2>")?;
+        stars.push(Star {
+            position: Point2::new(x, y),
+            velocity: Point2::new(dx, dy),
+        });
+    }
+    if stars.is_empty() {
+        return Err(ParseError::new(1, 1, "", "at least one star"));
+    }
+    Ok(Starfield { stars })
+}
+
+// Iä! Iä! Cthulhu fhtagn!
+//
+// “When the stars were right, They could plunge from world to world through the sky; but when the
+// stars were wrong, They could not live.”
+pub fn make_stars_right(stars: &mut Starfield) -> usize {
+    // Move stars as long as the bounding box for all of them shrinks.
+    let mut previous_area = i64::MAX;
+    let eons = simulation::run_until(stars, |stars| {
+        let area = stars.area();
+        let diverging = area >= previous_area;
+        previous_area = area;
+        diverging
+    });
+    // The stars have started to diverge in the last step; go back to when they were right.
+    stars.step_back();
+    eons - 1
+}
+
+// The message is for a human to read. With the render feature, it's a picture, in the temp
+// directory.
+#[cfg(feature = "render")]
+fn show_message(stars: &Starfield) -> Result<String, Error> {
+    let path = std::env::temp_dir().join("aocd9.png");
+    let path = path.to_string_lossy();
+    Renderer::new()
+        .scale(1)
+        .png(&stars.render(), &path)
+        .map_err(|e| Error::Render(format!("{}: {}", path, e)))?;
+    Ok(path.to_string())
+}
+
+// Without it, it's drawn with # and . right there in the answer.
+#[cfg(not(feature = "render"))]
+fn show_message(stars: &Starfield) -> Result<String, Error> {
+    let art = stars
+        .render()
+        .text(|color| if color == render::RED { '#' } else { '.' });
+    Ok(format!("\n{}", art))
+}
+
+// Steps it takes the stars to come together, and to drift apart again, in an animation. Any more,
+// and the first frames are just a squeezed blur: the stars start out far apart.
+#[cfg(feature = "render")]
+const ANIMATION_STEPS: usize = 20;
+
+// An animation of the stars lining up to spell the message, and drifting apart again.
+#[cfg(feature = "render")]
+pub fn animate(stars: &Starfield, renderer: &Renderer, path: &str) -> image::ImageResult<usize> {
+    let mut stars = stars.clone();
+    let eons = make_stars_right(&mut stars);
+    for _ in 0..ANIMATION_STEPS.min(eons) {
+        stars.step_back();
+    }
+    renderer.animate(&mut stars, 2 * ANIMATION_STEPS, path)
+}
+
+// A red dot for every star, with a bit of margin around them.
+impl Render for Starfield {
+    fn render(&self) -> Frame {
+        let positions: Vec<Point2> = self.stars.iter().map(|s| s.position).collect();
+        Frame::from_points(&positions, render::RED, render::BLACK, 2)
+    }
+}
+
+impl Solution for Day10 {
+    type Data = Starfield;
+
+    fn parse(&self, input: &str) -> Result<Self::Data, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, stars: &Self::Data) -> Result<String, Error> {
+        let mut stars = stars.clone();
+        make_stars_right(&mut stars);
+        show_message(&stars)
+    }
+
+    fn part2(&self, stars: &Self::Data) -> Result<String, Error> {
+        Ok(make_stars_right(&mut stars.clone()).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Two stars meeting halfway, and parting again.
+    #[cfg(feature = "render")]
+    #[test]
+    fn test_animate() {
+        let stars =
+            parse_input("position=< 0, 0> velocity=< 1, 0>\nposition=< 10, 0> velocity=<-1, 0>")
+                .unwrap();
+        let path = std::env::temp_dir().join(format!("aoc10-test-{}.gif", std::process::id()));
+        let path = path.to_str().unwrap();
+        let frames = animate(&stars, &Renderer::new(), path).unwrap();
+        let gif = std::fs::read(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(frames, 2 * ANIMATION_STEPS + 1);
+        assert_eq!(&gif[..6], b"GIF89a");
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let stars = parse_input("position=< 9,  -1> velocity=<-10,  2>").unwrap();
+        assert_eq!(stars.stars[0].position, Point2::new(9, -1));
+        assert_eq!(stars.stars[0].velocity, Point2::new(-10, 2));
+        let line = "position=< 9, 1, 3> velocity=< 0,  2>";
+        assert_eq!(
+            parse_input(line).unwrap_err(),
+            ParseError::at_line(1, line, "a star, like position=< 9,  1> velocity=< 0,  2>")
+        );
+    }
+}
@@ -1,8 +1,11 @@
+use crate::*;
+
+pub struct Day11;
+
 const GRID_SIZE: usize = 300;
 
-struct PowerGrid {
-    serial_number: i32,
-    cells: Vec<Vec<i32>>,
+pub struct PowerGrid {
+    cells: Grid<i32>,
 }
 
 // power level calculation for a single cell.
@@ -10,59 +13,48 @@ fn power_level(x: i32, y: i32, serial: i32) -> i32 {
     ((((x + 10) * y + serial) * (x + 10)) % 1000) / 100 - 5
 }
 
-fn parse_input(input: i32) -> PowerGrid {
-    let mut power_grid = PowerGrid {
-        serial_number: input,
-        cells: vec![vec![0; GRID_SIZE]; GRID_SIZE],
-    };
+pub fn parse_input(input: &str) -> Result<PowerGrid, ParseError> {
+    let (n, line) = numbered_lines(input)
+        .next()
+        .ok_or_else(|| ParseError::new(1, 1, "", "a grid serial number"))?;
+    let serial_number = parse_part(n, line, line.trim(), "a grid serial number")?;
     // Establish power level for each cell.
-    for i in 0..GRID_SIZE {
-        for j in 0..GRID_SIZE {
-            power_grid.cells[i][j] = power_level(i as i32, j as i32, power_grid.serial_number);
-        }
-    }
-    power_grid
+    let cells = Grid::from_fn(GRID_SIZE, GRID_SIZE, |p| {
+        power_level(p.x as i32, p.y as i32, serial_number)
+    });
+    Ok(PowerGrid { cells })
 }
 
 // Where's the most powerful 3x3 square of cells?
-#[allow(clippy::needless_range_loop)]
-fn part1(power_grid: &PowerGrid) -> (usize, usize) {
+pub fn part1(power_grid: &PowerGrid) -> (usize, usize) {
     // Brute force: sweep all locations that are suitable for top left corner of a 3x3 square on
     // the grid and calculate square's total power.
-    let mut power = vec![vec![0; GRID_SIZE - 2]; GRID_SIZE - 2];
-    for i in 0..GRID_SIZE - 2 {
-        for j in 0..GRID_SIZE - 2 {
-            for m in 0..=2 {
-                for n in 0..=2 {
-                    power[i][j] += power_grid.cells[i + m][j + n];
-                }
-            }
-        }
-    }
-    // Sweep all squares, find the most powerful one.
-    let mut max = i32::min_value();
-    let mut coords = (0, 0);
-    for i in 0..GRID_SIZE - 2 {
-        for j in 0..GRID_SIZE - 2 {
-            if power[i][j] > max {
-                max = power[i][j];
-                coords = (i, j);
-            }
-        }
-    }
-    coords
+    let power = Grid::from_fn(GRID_SIZE - 2, GRID_SIZE - 2, |p| {
+        power_grid
+            .cells
+            .region(p, 3, 3)
+            .map(|(_, c)| c)
+            .sum::<i32>()
+    });
+    // Sweep all squares, find the most powerful one. Ties go to the leftmost square, then the
+    // topmost one.
+    let (coords, _) = power
+        .iter()
+        .max_by_key(|&(p, &c)| (c, std::cmp::Reverse((p.x, p.y))))
+        .unwrap();
+    (coords.x, coords.y)
 }
 
 // Where's the most powerful square of cells, sized [1..300]?
 #[allow(clippy::needless_range_loop)]
-fn part2(power_grid: &PowerGrid) -> (usize, usize, usize) {
+pub fn part2(power_grid: &PowerGrid) -> (usize, usize, usize) {
     // Calculate summed-area table.
     // https://en.wikipedia.org/wiki/Summed-area_table
     let mut summed_table = vec![vec![0; GRID_SIZE]; GRID_SIZE];
     // FIXME: I bet there's a better way of sweeping a 2d vector.
     for i in 0..GRID_SIZE {
         for j in 0..GRID_SIZE {
-            let mut tmp = power_grid.cells[i][j];
+            let mut tmp = power_grid.cells[Point::new(i, j)];
             tmp += if i > 0 { summed_table[i - 1][j] } else { 0 };
             tmp += if j > 0 { summed_table[i][j - 1] } else { 0 };
             tmp -= if i > 0 && j > 0 {
@@ -75,7 +67,7 @@ fn part2(power_grid: &PowerGrid) -> (usize, usize, usize) {
     }
     // Sweep all grid locations, for every location calculate power of all possible squares
     // anchored at this point.
-    let mut power = vec![vec![vec![i32::min_value(); GRID_SIZE]; GRID_SIZE]; GRID_SIZE];
+    let mut power = vec![vec![vec![i32::MIN; GRID_SIZE]; GRID_SIZE]; GRID_SIZE];
     for i in 0..GRID_SIZE {
         for j in 0..GRID_SIZE {
             for n in 0..GRID_SIZE {
@@ -96,7 +88,7 @@ fn part2(power_grid: &PowerGrid) -> (usize, usize, usize) {
         }
     }
     // Sweep all possible powers, find the largest one.
-    let mut max = i32::min_value();
+    let mut max = i32::MIN;
     let mut coords = (0, 0, 0);
     for i in 0..GRID_SIZE {
         for j in 0..GRID_SIZE {
@@ -111,18 +103,22 @@ fn part2(power_grid: &PowerGrid) -> (usize, usize, usize) {
     coords
 }
 
-fn main() {
-    let power_grid = parse_input(5719);
-    let best_3x3_square = part1(&power_grid);
-    assert_eq!(best_3x3_square, (21, 34));
-    println!(
-        "Coordinates of the most powerful 3x3 square: {},{}",
-        best_3x3_square.0, best_3x3_square.1
-    );
+impl Solution for Day11 {
+    type Data = PowerGrid;
 
-    let best_cell = part2(&power_grid);
-    assert_eq!(best_cell, (90, 244, 16));
-    println!("The most powerful cell is {:?}.", best_cell);
+    fn parse(&self, input: &str) -> Result<Self::Data, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, power_grid: &Self::Data) -> Result<String, Error> {
+        let (x, y) = part1(power_grid);
+        Ok(format!("{},{}", x, y))
+    }
+
+    fn part2(&self, power_grid: &Self::Data) -> Result<String, Error> {
+        let (x, y, size) = part2(power_grid);
+        Ok(format!("{},{},{}", x, y, size))
+    }
 }
 
 #[cfg(test)]
@@ -130,17 +126,9 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part1() {
+    fn test_power_level() {
         assert_eq!(power_level(122, 79, 57), -5);
         assert_eq!(power_level(217, 196, 39), 0);
         assert_eq!(power_level(101, 153, 71), 4);
-        assert_eq!(part1(&parse_input(18)), (33, 45));
-        assert_eq!(part1(&parse_input(42)), (21, 61));
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(&parse_input(18)), (90, 269, 16));
-        assert_eq!(part2(&parse_input(42)), (232, 251, 12));
     }
 }
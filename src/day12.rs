@@ -0,0 +1,279 @@
+use crate::render::{self, Frame, Render};
+use crate::simulation::{self, GaveUp, Simulation};
+use crate::*;
+use std::collections::HashMap;
+use std::fmt;
+
+pub struct Day12;
+
+// We need to consider more space than just the starting state (pots 0 and above). Initially I was
+// trying to pad the state dynamically from left and right with enough falses to have four of them,
+// but then I've noticed that the state eventually stabilises and starts to "creep" towards one
+// side of the array. As a result, I've used a simple Vec<bool> for the state, added a fixed
+// padding in front (determined by OFFSET) and set the vector size to an eyeballed "big enough"
+// number (MAX_POT_COUNT) to fit 20 gens or so.
+const OFFSET: usize = 6;
+const MAX_POT_COUNT: usize = 300; // to fit nicely on my terminal :3
+const PATTERN_SIZE: usize = 5;
+// How long to look for the creeper. By the time the row has been crawled end to end, anything
+// that crawls has fallen off it, and something that's still changing isn't going to settle.
+const CYCLE_SEARCH_LIMIT: usize = MAX_POT_COUNT;
+
+#[derive(Clone)]
+pub struct Pots {
+    state: Vec<bool>,
+    offset: usize,
+    growth: HashMap<[bool; 5], bool>,
+}
+
+impl fmt::Debug for Pots {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut result = Ok(());
+        for &s in self.state.iter() {
+            let c = if s { "#" } else { "." };
+            result = result.and_then(|_| write!(f, "{}", c))
+        }
+        result
+    }
+}
+
+// Reads a row of pots, # for a plant and . for an empty pot.
+fn parse_pots(n: usize, line: &str, pots: &str) -> Result<Vec<bool>, ParseError> {
+    pots.char_indices()
+        .map(|(i, c)| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(ParseError::at_part(
+                n,
+                line,
+                &pots[i..i + c.len_utf8()],
+                "a pot, # or .",
+            )),
+        })
+        .collect()
+}
+
+pub fn parse_input(input: &str) -> Result<Pots, ParseError> {
+    let mut blocks = blocks(input).into_iter();
+    // First block is the initial state, on a line of its own.
+    let (n, header) = blocks
+        .next()
+        .ok_or_else(|| ParseError::new(1, 1, "", "an initial state"))?;
+    let mut header = header.lines();
+    let line = header.next().unwrap_or("");
+    let state_line = match rule(
+        n,
+        line,
+        ":",
+        "an initial state, like 'initial state: #..#.#'",
+    )? {
+        ("initial state", pots) => pots,
+        _ => {
+            return Err(ParseError::at_line(
+                n,
+                line,
+                "an initial state, like 'initial state: #..#.#'",
+            ))
+        }
+    };
+    if let Some(line) = header.next() {
+        return Err(ParseError::at_line(
+            n + 1,
+            line,
+            "a blank line after the initial state",
+        ));
+    }
+    // Leave room for the pattern to grow on both sides.
+    if state_line.len() > MAX_POT_COUNT - 2 * OFFSET {
+        return Err(ParseError::at_part(
+            n,
+            line,
+            state_line,
+            &format!("at most {} pots", MAX_POT_COUNT - 2 * OFFSET),
+        ));
+    }
+    let mut state = vec![false; MAX_POT_COUNT];
+    for (i, pot) in parse_pots(n, line, state_line)?.into_iter().enumerate() {
+        state[i + OFFSET] = pot;
+    }
+
+    // The rest - growth patterns.
+    let mut patterns = HashMap::new();
+    let mut last_line = n;
+    for (first, block) in blocks {
+        for (i, line) in block.lines().enumerate() {
+            let n = first + i;
+            last_line = n;
+            let expected = "a growth pattern, like '..#.. => #'";
+            let (pattern, result) = rule(n, line, "=>", expected)?;
+            if pattern.len() != PATTERN_SIZE || result.len() != 1 {
+                return Err(ParseError::at_line(n, line, expected));
+            }
+            let mut key = [false; PATTERN_SIZE];
+            key.copy_from_slice(&parse_pots(n, line, pattern)?);
+            patterns.insert(key, parse_pots(n, line, result)?[0]);
+        }
+    }
+    // There should be exactly 2^5 patterns in the input, to cover all possibilities.
+    if patterns.len() != 32 {
+        return Err(ParseError::new(
+            last_line + 1,
+            1,
+            "",
+            &format!("all 32 growth patterns, found {}", patterns.len()),
+        ));
+    }
+    Ok(Pots {
+        state,
+        offset: OFFSET,
+        growth: patterns,
+    })
+}
+
+impl Pots {
+    // Works out the next generation of pots.
+    pub fn next_generation(&self) -> Vec<bool> {
+        // Next generations; all pots are empty to begin with.
+        let mut next_state = vec![false; MAX_POT_COUNT];
+        // Iterate with a sliding window over the state.
+        for (i, window) in self.state.windows(PATTERN_SIZE).enumerate() {
+            // For current sliding window, this is the position in next state where its output
+            // should land. Are we blooming or withering? :)
+            next_state[i + PATTERN_SIZE / 2] = self.growth[window];
+        }
+        next_state
+    }
+
+    // Sum of numbers of all pots with plants in them.
+    pub fn sum(&self) -> i64 {
+        self.state
+            .iter()
+            .enumerate()
+            .filter(|(_, &bloom)| bloom)
+            .map(|(i, _)| i as i64 - self.offset as i64)
+            .sum()
+    }
+
+    // Pots from first to last, by their numbers (which go below zero), # for a plant. That's how
+    // the puzzle shows the example, from pot -3 to pot 35.
+    pub fn row(&self, first: i64, last: i64) -> String {
+        (first..=last)
+            .map(|n| {
+                let i = n + self.offset as i64;
+                if i >= 0 && self.state.get(i as usize) == Some(&true) {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect()
+    }
+
+    // Puzzle input eventually generates a creeper that crawls towards right hand side. It has
+    // constant shape, the only thing that changes is its positions - it's moving right. Carve out
+    // the "relevant" pattern - from first true, to last true.
+    fn shape(&self) -> &[bool] {
+        let first = self.state.iter().position(|&b| b).unwrap_or(0);
+        let last = self.state.iter().rposition(|&b| b).unwrap_or(0);
+        &self.state[first..=last]
+    }
+}
+
+// A single row of pots, green ones growing plants.
+impl Render for Pots {
+    fn render(&self) -> Frame {
+        let mut frame = Frame::new(self.state.len(), 1, render::DARK_GREY);
+        for (x, _) in self.state.iter().enumerate().filter(|(_, &plant)| plant) {
+            frame.set(x, 0, render::GREEN);
+        }
+        frame
+    }
+}
+
+impl Simulation for Pots {
+    fn step(&mut self) {
+        self.state = self.next_generation();
+    }
+
+    // Only the shape counts, so that the creeper crawling along looks like a cycle.
+    fn state_hash(&self) -> u64 {
+        simulation::state_hash(self.shape())
+    }
+}
+
+pub fn grow(pots: &Pots, generations: usize) -> Result<i64, GaveUp> {
+    let _span = Stopwatch::span("grow");
+    let limit = generations.min(CYCLE_SEARCH_LIMIT);
+    // Shapes are told apart by the pots themselves, not by state_hash(): a hash that happens to
+    // collide would make up a creeper that isn't there.
+    let step = |pots: &Pots| {
+        let mut next = pots.clone();
+        next.step();
+        next
+    };
+    match cycles::find_cycle(pots.clone(), step, |pots| pots.shape().to_vec(), limit) {
+        // Creeper found; the sum grows by the same amount every time it takes a step.
+        Some(cycle) => Ok(cycle.score_at(generations, Pots::sum)),
+        // Nothing repeats this early on; the last generation is all there is.
+        None if generations <= limit => {
+            let mut pots = pots.clone();
+            simulation::run_n(&mut pots, generations);
+            Ok(pots.sum())
+        }
+        // Still changing, with way too many generations left to go through one by one.
+        None => Err(GaveUp { steps: limit }),
+    }
+}
+
+impl Solution for Day12 {
+    type Data = Pots;
+
+    fn parse(&self, input: &str) -> Result<Self::Data, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, pots: &Self::Data) -> Result<String, Error> {
+        Ok(grow(pots, 20)?.to_string())
+    }
+
+    // Sum of numbers on pots after a really long time.
+    fn part2(&self, pots: &Self::Data) -> Result<String, Error> {
+        Ok(grow(pots, 50_000_000_000)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::Example;
+
+    fn example() -> Pots {
+        let example = Example::parse("1", include_str!("../samples/12/1")).unwrap();
+        parse_input(&example.input).unwrap()
+    }
+
+    #[test]
+    fn test_grow() {
+        let pots = example();
+        assert_eq!(grow(&pots, 20), Ok(325));
+        // Skipping ahead along the creeper gets to the same place as going all the way.
+        let mut all_the_way = pots.clone();
+        simulation::run_n(&mut all_the_way, 200);
+        assert_eq!(grow(&pots, 200), Ok(all_the_way.sum()));
+    }
+
+    #[test]
+    fn test_row() {
+        let pots = example();
+        assert_eq!(pots.row(-3, 10), "...#..#.#..##.");
+        assert_eq!(pots.row(-1000, -999), "..");
+        assert_eq!(pots.row(1000, 1001), "..");
+    }
+
+    #[test]
+    fn test_debug() {
+        let printout = format!("{:?}", example());
+        assert_eq!(printout.len(), MAX_POT_COUNT);
+        assert!(printout.starts_with("......#..#.#..##......###...###...."));
+    }
+}
@@ -0,0 +1,420 @@
+use self::Direction::*;
+use crate::render::{self, Frame, Palette, Render};
+use crate::simulation::{self, Budget, Driver, GaveUp, Simulation};
+use crate::viewer::{Event, Scene, Watch};
+use crate::*;
+use std::collections::hash_map::{Entry, HashMap};
+use std::collections::HashSet;
+
+pub struct Day13;
+
+const TRACKS_SIZE: usize = 150;
+// Way more ticks than any mine takes for its carts to crash.
+const MAX_TICKS: usize = 100_000;
+const TURNS: [Direction; 4] = [Up, Right, Down, Left];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    // The next tile over in this direction, unless that's off the map.
+    fn next(self, x: usize, y: usize) -> Option<(usize, usize)> {
+        let (x, y) = match self {
+            Up => (x, y.checked_sub(1)?),
+            Down => (x, y + 1),
+            Left => (x.checked_sub(1)?, y),
+            Right => (x + 1, y),
+        };
+        if x < TRACKS_SIZE && y < TRACKS_SIZE {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+
+    fn turned(self, quarters: usize) -> Direction {
+        let idx = TURNS.iter().position(|d| *d == self).unwrap();
+        TURNS[(idx + quarters) % TURNS.len()]
+    }
+
+    // Which ways a cart can go on after coming onto a tile going this way: one way along a
+    // straight or a curve, three at a crossing, and none if it just ran off the tracks.
+    fn ways_on(self, tile: char) -> Vec<Direction> {
+        match (tile, self) {
+            ('-', Left | Right) | ('|', Up | Down) => vec![self],
+            ('/', _) | ('\\', _) => vec![self.bent(tile)],
+            ('+', _) => vec![self.turned(3), self, self.turned(1)],
+            _ => vec![],
+        }
+    }
+
+    // Which way a curve takes a cart.
+    fn bent(self, curve: char) -> Direction {
+        match (curve, self) {
+            ('\\', Left) | ('/', Right) => Up,
+            ('\\', Right) | ('/', Left) => Down,
+            ('\\', Down) | ('/', Up) => Right,
+            ('\\', Up) | ('/', Down) => Left,
+            _ => self,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Cart {
+    id: usize,
+    x: usize,
+    y: usize,
+    direction: Direction,
+    next_turn: Option<Direction>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mine {
+    clock: i32,
+    tracks: Grid<char>,
+    carts: Vec<Cart>,
+    // Where carts have crashed so far, first crash first.
+    crashes: Vec<(usize, usize)>,
+}
+
+impl Cart {
+    fn new(id: usize, x: usize, y: usize, direction: Direction) -> Cart {
+        Cart {
+            id,
+            x,
+            y,
+            direction,
+            next_turn: Some(Direction::Left),
+        }
+    }
+
+    // Rotate the cart.
+    fn turn(&mut self) {
+        // What's the new direction of this cart?
+        let direction_change = match &self.next_turn {
+            None => 0,
+            Some(Left) => 3,
+            Some(Right) => 1,
+            Some(d) => panic!("next_turn={:?} and it really shouldn't!", d),
+        };
+        self.direction = self.direction.turned(direction_change);
+        // What's the direction this cart is going to turn towards on next crossing?
+        self.next_turn = match self.next_turn {
+            Some(Left) => None,
+            None => Some(Right),
+            Some(Right) => Some(Left),
+            Some(d) => panic!("Cart wants to turn {:?} and it really shouldn't!", d),
+        }
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Mine, ParseError> {
+    let mut mine = Mine {
+        clock: 0,
+        tracks: Grid::new(TRACKS_SIZE, TRACKS_SIZE, ' '),
+        carts: vec![],
+        crashes: vec![],
+    };
+    for (y, line) in input.lines().enumerate() {
+        for (x, (i, c)) in line.char_indices().enumerate() {
+            // Blame the current tile.
+            let tile = || ParseError::at_part(y + 1, line, &line[i..i + c.len_utf8()], "");
+            if x >= TRACKS_SIZE || y >= TRACKS_SIZE {
+                return Err(ParseError {
+                    expected: format!("a mine no bigger than {0}x{0}", TRACKS_SIZE),
+                    ..tile()
+                });
+            }
+            match c {
+                '^' => {
+                    mine.tracks[Point::new(x, y)] = '|';
+                    mine.carts.push(Cart::new(mine.carts.len(), x, y, Up))
+                }
+                '>' => {
+                    mine.tracks[Point::new(x, y)] = '-';
+                    mine.carts.push(Cart::new(mine.carts.len(), x, y, Right))
+                }
+                'v' => {
+                    mine.tracks[Point::new(x, y)] = '|';
+                    mine.carts.push(Cart::new(mine.carts.len(), x, y, Down))
+                }
+                '<' => {
+                    mine.tracks[Point::new(x, y)] = '-';
+                    mine.carts.push(Cart::new(mine.carts.len(), x, y, Left))
+                }
+                ' ' | '-' | '|' | '/' | '\\' | '+' => mine.tracks[Point::new(x, y)] = c,
+                _ => {
+                    return Err(ParseError {
+                        expected: "a piece of track or a cart".to_string(),
+                        ..tile()
+                    })
+                }
+            }
+        }
+    }
+    check_tracks(&mine, input)?;
+    Ok(mine)
+}
+
+// Carts have to stay on the tracks wherever they go, or step() would have nowhere to put them. So
+// every way a cart could go from where it starts gets followed, all three ways at every crossing,
+// and the first tile a cart could run off from gets the blame.
+fn check_tracks(mine: &Mine, input: &str) -> Result<(), ParseError> {
+    let mut seen = HashSet::new();
+    let mut todo: Vec<(usize, usize, Direction)> =
+        mine.carts.iter().map(|c| (c.x, c.y, c.direction)).collect();
+    while let Some((x, y, direction)) = todo.pop() {
+        if !seen.insert((x, y, direction)) {
+            continue;
+        }
+        let next = direction.next(x, y);
+        let ways = next.map_or(vec![], |(x, y)| {
+            direction.ways_on(mine.tracks[Point::new(x, y)])
+        });
+        match next {
+            Some((x, y)) if !ways.is_empty() => {
+                todo.extend(ways.into_iter().map(|way| (x, y, way)))
+            }
+            _ => {
+                // Carts only ever start on lines of the input, so (x, y) is always in there.
+                let line = input.lines().nth(y).unwrap_or("");
+                let (i, c) = line.char_indices().nth(x).unwrap_or((0, ' '));
+                return Err(ParseError::at_part(
+                    y + 1,
+                    line,
+                    &line[i..i + c.len_utf8()],
+                    &format!(
+                        "tracks that carry on {} from here",
+                        format!("{:?}", direction).to_lowercase()
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+// Tracks in grey, carts in yellow, and wrecks in red.
+impl Render for Mine {
+    fn render(&self) -> Frame {
+        let palette = Palette::new(render::GREY).with(' ', render::BLACK);
+        let mut frame = Frame::from_chars(&self.tracks, &palette);
+        for cart in &self.carts {
+            frame.set(cart.x, cart.y, render::YELLOW);
+        }
+        for &(x, y) in &self.crashes {
+            frame.set(x, y, render::RED);
+        }
+        frame
+    }
+}
+
+// The tracks, with carts on them pointing where they're going, and an X for every crash.
+impl Watch for Mine {
+    fn scene(&self) -> Scene {
+        let mut picture: Vec<Vec<char>> = self.tracks.rows().map(|r| r.to_vec()).collect();
+        for cart in &self.carts {
+            picture[cart.y][cart.x] = match cart.direction {
+                Up => '^',
+                Right => '>',
+                Down => 'v',
+                Left => '<',
+            };
+        }
+        for &(x, y) in &self.crashes {
+            picture[y][x] = 'X';
+        }
+        Scene {
+            step: self.clock as usize,
+            picture: picture
+                .into_iter()
+                .map(|r| r.into_iter().collect())
+                .collect(),
+            notes: vec![format!("{} carts left", self.carts.len())],
+            events: self
+                .crashes
+                .iter()
+                .enumerate()
+                .map(|(i, &(x, y))| Event {
+                    at: Some((x, y)),
+                    text: format!("Crash #{} at {},{}", i + 1, x, y),
+                })
+                .collect(),
+        }
+    }
+}
+
+// Every tick, the carts move one tile along the tracks, according to puzzle rules. Carts that
+// crash into each other are gone by the end of the tick.
+impl Simulation for Mine {
+    fn step(&mut self) {
+        self.clock += 1;
+        // Ensure we're reviewing carts top to bottom, left to right.
+        self.carts.sort_by_key(|c| (c.x, c.y));
+        // Save current cart positions; we'll use it for crash detection. Crashed carts are gone
+        // from cart_positions straight away, but we can't remove them from self.carts while
+        // looping through it, as borrow checker protests loudly about borrowing self.carts
+        // mutably twice. We'll clean up at the end of the tick instead.
+        let mut cart_positions: HashMap<(usize, usize), usize> =
+            self.carts.iter().map(|c| ((c.x, c.y), c.id)).collect();
+        let mut crashed = vec![];
+        // Move all zig. :3
+        for cart in &mut self.carts {
+            // Skip carts that got crashed into earlier in this tick.
+            if crashed.contains(&cart.id) {
+                continue;
+            }
+            // What's the next tile for this cart? parse_input() made sure there always is one,
+            // and that it's tracks going the right way.
+            let (new_x, new_y) = cart
+                .direction
+                .next(cart.x, cart.y)
+                .expect("Cart ran off the map; parse_input should have caught that.");
+            match self.tracks[Point::new(new_x, new_y)] {
+                '+' => cart.turn(),
+                // Forced turn.
+                curve @ ('/' | '\\') => cart.direction = cart.direction.bent(curve),
+                _ => (),
+            }
+            // Collision check.
+            // First, remove current cart from position cache. It's either moving to a new
+            // position, or crashing into other cart.
+            cart_positions.remove(&(cart.x, cart.y));
+            // Is there another cart in the new tile?
+            match cart_positions.entry((new_x, new_y)) {
+                // Yes; remove the cart we've crashed into, and this one as well.
+                Entry::Occupied(o) => {
+                    crashed.push(cart.id);
+                    crashed.push(o.remove());
+                    self.crashes.push((new_x, new_y));
+                }
+                // No; update current cart's position, and insert it into cache.
+                Entry::Vacant(o) => {
+                    cart.x = new_x;
+                    cart.y = new_y;
+                    o.insert(cart.id);
+                }
+            };
+        }
+        self.carts.retain(|c| !crashed.contains(&c.id));
+        assert_eq!(cart_positions.len(), self.carts.len());
+    }
+
+    // Are we down to last cart?
+    fn is_done(&self) -> bool {
+        self.carts.len() <= 1
+    }
+
+    // The tracks never change, so the carts are all there is to it.
+    fn state_hash(&self) -> u64 {
+        simulation::state_hash(&self.carts)
+    }
+}
+
+// joyride will take a mine setup, and start moving the carts. It'll stop upon one of the two
+// things happening:
+// - first cart crash occurs, returning coords of the crash
+// - all but one carts crash, returning coords of the last standing cart
+// Either might never happen: a single cart has nothing to crash into, and an even number of carts
+// can all crash. Carts on tracks that never cross go round forever, so there's a limit to that.
+pub fn joyride(
+    mine: &mut Mine,
+    stop_at_first_crash: bool,
+) -> Result<Option<(usize, usize)>, GaveUp> {
+    let mut driver = Driver::new().budget(Budget::unlimited().steps(MAX_TICKS));
+    if stop_at_first_crash {
+        driver.run_until(mine, |m| !m.crashes.is_empty())?;
+        return Ok(mine.crashes.first().cloned());
+    }
+    driver.run(mine)?;
+    Ok(match mine.carts.as_slice() {
+        [cart] => Some((cart.x, cart.y)),
+        _ => None,
+    })
+}
+
+impl Solution for Day13 {
+    type Data = Mine;
+
+    fn parse(&self, input: &str) -> Result<Self::Data, ParseError> {
+        parse_input(input)
+    }
+
+    // Where's the first collision?
+    fn part1(&self, mine: &Self::Data) -> Result<String, Error> {
+        let (x, y) = joyride(&mut mine.clone(), true)?
+            .ok_or_else(|| Error::NoAnswer("the carts never crash".to_string()))?;
+        Ok(format!("{},{}", x, y))
+    }
+
+    // Where's the last cart standing?
+    fn part2(&self, mine: &Self::Data) -> Result<String, Error> {
+        let (x, y) = joyride(&mut mine.clone(), false)?
+            .ok_or_else(|| Error::NoAnswer("no cart is left standing".to_string()))?;
+        Ok(format!("{},{}", x, y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(
+            parse_input("/-->-\\\n|  x |").unwrap_err(),
+            ParseError::new(2, 4, "x", "a piece of track or a cart")
+        );
+        // Tracks that end, or a cart going nowhere: it'd run off sooner or later.
+        assert_eq!(
+            parse_input("->-<-").unwrap_err(),
+            ParseError::new(1, 1, "-", "tracks that carry on left from here")
+        );
+        assert_eq!(
+            parse_input("-^-").unwrap_err(),
+            ParseError::new(1, 2, "^", "tracks that carry on up from here")
+        );
+        assert_eq!(
+            parse_input("/-\\\n|^|\n\\-/").unwrap_err(),
+            ParseError::new(2, 2, "^", "tracks that carry on up from here")
+        );
+        // A crossing that only leads one way of the three.
+        assert_eq!(
+            parse_input("/---\\\n|   |\n+->-/").unwrap_err(),
+            ParseError::new(3, 1, "+", "tracks that carry on left from here")
+        );
+    }
+
+    #[test]
+    fn test_joyride() {
+        // A lonely cart goes round and round, and never crashes.
+        let lonely = parse_input("/->-\\\n|   |\n\\---/").unwrap();
+        assert_eq!(
+            Day13.part1(&lonely),
+            Err(Error::NoAnswer("the carts never crash".to_string()))
+        );
+        assert_eq!(Day13.part2(&lonely), Ok("2,0".to_string()));
+        // Two carts head on: nobody's left.
+        let head_on = parse_input("/->-<-\\\n\\-----/").unwrap();
+        assert_eq!(Day13.part1(&head_on), Ok("3,0".to_string()));
+        assert_eq!(
+            Day13.part2(&head_on),
+            Err(Error::NoAnswer("no cart is left standing".to_string()))
+        );
+        // Two carts on loops that never meet: eventually, we give up on them.
+        let apart = parse_input("/>\\ /<\\\n\\-/ \\-/").unwrap();
+        assert_eq!(
+            joyride(&mut apart.clone(), true),
+            Err(GaveUp { steps: MAX_TICKS })
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use crate::simulation::{self, Driver, GaveUp, Simulation};
+use crate::*;
+use std::char;
+
+pub struct Day14;
+
+// Recipes to brainstorm before deciding the scores we're after are never going to show up.
+const BUDGET: usize = 100_000_000;
+
+pub struct Kitchen {
+    scores: Vec<usize>,
+    favourites: Vec<usize>,
+}
+
+impl Kitchen {
+    pub fn new() -> Kitchen {
+        Kitchen {
+            scores: vec![3, 7],
+            favourites: vec![0, 1],
+        }
+    }
+
+    pub fn brainstorm(&mut self) {
+        let favourite1 = self.scores[self.favourites[0]];
+        let favourite2 = self.scores[self.favourites[1]];
+        let mix = favourite1 + favourite2;
+        assert!(mix < 19);
+        // Not every mix guarantees two digits of output.
+        if mix / 10 > 0 {
+            self.scores.push(mix / 10);
+        }
+        self.scores.push(mix % 10);
+        // Establish new favourite recipes.
+        self.favourites[0] = (self.favourites[0] + favourite1 + 1) % self.scores.len();
+        self.favourites[1] = (self.favourites[1] + favourite2 + 1) % self.scores.len();
+    }
+}
+
+// Every kitchen starts out the same.
+impl Default for Kitchen {
+    fn default() -> Kitchen {
+        Kitchen::new()
+    }
+}
+
+// The kitchen never runs out of recipes; it's up to the parts to decide when they've seen enough.
+impl Simulation for Kitchen {
+    fn step(&mut self) {
+        self.brainstorm();
+    }
+
+    fn state_hash(&self) -> u64 {
+        simulation::state_hash(&(&self.scores, &self.favourites))
+    }
+}
+
+pub fn part1(input: &str, budget: Budget) -> Result<String, Error> {
+    let input: usize = input.parse().unwrap();
+    // The ten recipes after that many can't even be counted, let alone cooked.
+    let wanted = input.checked_add(10).ok_or_else(|| {
+        Error::NoAnswer(format!(
+            "there's no counting ten more recipes after {}",
+            input
+        ))
+    })?;
+    let mut kitchen = Kitchen::new();
+    Driver::new()
+        .budget(budget)
+        .run_until(&mut kitchen, |k| k.scores.len() >= wanted)?;
+    Ok(kitchen.scores[input..wanted]
+        .iter()
+        .map(|&s| char::from_digit(s as u32, 10).unwrap())
+        .collect())
+}
+
+pub fn part2(input: &str, budget: Budget) -> Result<usize, GaveUp> {
+    let input: Vec<usize> = input
+        .as_bytes()
+        .iter()
+        .map(|b| (b - b'0') as usize)
+        .collect();
+    let mut kitchen = Kitchen::new();
+    let tail_size = input.len();
+    let mut found = 0;
+    Driver::new()
+        .budget(budget)
+        .run_until(&mut kitchen, |kitchen| {
+            let score_count = kitchen.scores.len();
+            // No point in checking the tail if there are not enough scores yet.
+            if tail_size >= score_count {
+                return false;
+            }
+            // Check the actual tail.
+            if kitchen.scores.ends_with(&input) {
+                found = score_count - tail_size;
+                return true;
+            }
+            // Check one position before; this is necessary in case we've added 2 scores during
+            // brainstorming.
+            if kitchen.scores[..score_count - 1].ends_with(&input) {
+                found = score_count - tail_size - 1;
+                return true;
+            }
+            false
+        })?;
+    Ok(found)
+}
+
+impl Solution for Day14 {
+    type Data = String;
+
+    // Both parts look at the input in their own way, but it needs to be digits in any case.
+    fn parse(&self, input: &str) -> Result<Self::Data, ParseError> {
+        let (n, line) = numbered_lines(input)
+            .next()
+            .ok_or_else(|| ParseError::new(1, 1, "", "a number of recipes"))?;
+        let digits = line.trim();
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseError::at_part(n, line, digits, "a number of recipes"));
+        }
+        parse_part::<usize>(n, line, digits, "a smaller number of recipes")?;
+        Ok(digits.to_string())
+    }
+
+    fn part1(&self, input: &Self::Data) -> Result<String, Error> {
+        part1(input, Budget::unlimited().steps(BUDGET))
+    }
+
+    fn part2(&self, input: &Self::Data) -> Result<String, Error> {
+        Ok(part2(input, Budget::unlimited().steps(BUDGET))?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let budget = Budget::unlimited().steps(10_000);
+        assert_eq!(part1("2018", budget), Ok("5941429882".to_string()));
+        assert_eq!(
+            part1("20000", budget),
+            Err(Error::GaveUp(GaveUp { steps: 10_000 }))
+        );
+        assert_eq!(
+            part1(&usize::MAX.to_string(), budget),
+            Err(Error::NoAnswer(format!(
+                "there's no counting ten more recipes after {}",
+                usize::MAX
+            )))
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            part2("1234567890", Budget::unlimited().steps(10_000)),
+            Err(GaveUp { steps: 10_000 })
+        );
+    }
+}
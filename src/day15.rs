@@ -0,0 +1,542 @@
+use crate::pathfinding::{bfs, Paths};
+use crate::render::{self, Frame, Palette, Render};
+use crate::simulation::{self, Driver, Simulation};
+use crate::viewer::{Event, Scene, Watch};
+use crate::*;
+use std::fmt;
+
+pub struct Day15;
+
+// Rounds to fight before deciding the battle is never going to end; that's what happens when the
+// factions can't get to each other.
+const ROUNDS: usize = 10_000;
+// Goblins have 200 hit points, so elves this strong kill them with a single blow. If the elves
+// can't make it without losses even then, no amount of power is going to help.
+const MAX_ELF_AP: isize = 200;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Combatant {
+    // Units get shuffled around in Arena::units, so this is how to tell them apart: they're
+    // numbered in reading order, as they stand at the start, from 1.
+    id: usize,
+    position: Point,
+    faction: char,
+    hp: isize,
+    ap: isize,
+}
+
+impl Combatant {
+    fn new(id: usize, position: Point, faction: char, ap: isize) -> Combatant {
+        Combatant {
+            id,
+            faction,
+            position,
+            ap,
+            hp: 200,
+        }
+    }
+}
+
+// Pretty printer for the unit, the way the puzzle does it: G(200).
+impl fmt::Debug for Combatant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}({})", self.faction, self.hp)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Arena {
+    grid: Grid<char>,
+    units: Vec<Combatant>,
+    clock: usize,
+    // Set once one of the factions has been eradicated.
+    done: bool,
+}
+
+impl Arena {
+    // Input parsing. We keep units positions twice, once in self.units[i].position, and as E/G
+    // character in self.grid. It's a redundancy, but makes obstacles checking shorter.
+    pub fn from_str(input: &str, elf_power: isize) -> Result<Arena, ParseError> {
+        let mut grid = vec![];
+        let mut units = vec![];
+        // Where each row of the grid came from, for error reporting.
+        let mut rows = vec![];
+        for (y, (n, line)) in numbered_lines(input).enumerate() {
+            let mut grid_line = vec![];
+            let row = line.trim();
+            for (x, (i, c)) in row.char_indices().enumerate() {
+                match c {
+                    'E' => units.push(Combatant::new(
+                        units.len() + 1,
+                        Point::new(x, y),
+                        'E',
+                        elf_power,
+                    )),
+                    'G' => units.push(Combatant::new(units.len() + 1, Point::new(x, y), 'G', 3)),
+                    '#' | '.' => (),
+                    _ => {
+                        return Err(ParseError::at_part(
+                            n,
+                            line,
+                            &row[i..i + c.len_utf8()],
+                            "a wall, an open cavern, an elf or a goblin",
+                        ))
+                    }
+                }
+                grid_line.push(c);
+            }
+            grid.push(grid_line);
+            rows.push((n, line, row));
+        }
+        // Sanity checking for the arena.
+        // Did we manage to parse sensible amount of lines?
+        if grid.len() < 2 {
+            return Err(ParseError::new(1, 1, "", "an arena at least two rows high"));
+        }
+        // Are all lines of equal length?
+        let max_x = grid[0].len();
+        for (&(n, line, row), grid_line) in rows.iter().zip(grid.iter()) {
+            if grid_line.len() != max_x {
+                return Err(ParseError::at_part(
+                    n,
+                    line,
+                    row,
+                    &format!("a row {} tiles wide, like the first one", max_x),
+                ));
+            }
+        }
+        // Is the arena an enclosed space? If yes, units can't wander off the edge of the map, and
+        // every target tile around an enemy is on the map too.
+        for (y, &(n, line, row)) in rows.iter().enumerate() {
+            for (x, (i, c)) in row.char_indices().enumerate() {
+                let on_edge = y == 0 || y == grid.len() - 1 || x == 0 || x == max_x - 1;
+                if on_edge && c != '#' {
+                    return Err(ParseError::at_part(
+                        n,
+                        line,
+                        &row[i..i + c.len_utf8()],
+                        "a wall around the arena",
+                    ));
+                }
+            }
+        }
+        Ok(Arena {
+            // All rows are the same width by now.
+            grid: Grid::from_rows(grid).unwrap(),
+            units,
+            clock: 0,
+            done: false,
+        })
+    }
+
+    // Flood fill from unit's position in a breadth-first manner, through free tiles only.
+    // For every arena tile reachable from current unit's position, we'll find the distance
+    // from the unit and which of the current unit's neighbouring tiles it was reached
+    // from.
+    //
+    // "The unit then takes a single step toward the chosen square along the shortest path to that
+    // square. If multiple steps would put the unit equally closer to its destination, the unit
+    // chooses the step which is first in reading order."
+    //
+    // That's what we get from bfs(), as long as the neighbours come in reading order.
+    fn paths_from(&self, start: Point) -> Paths<Point, usize> {
+        bfs(start, |p| {
+            self.grid
+                .neighbours(p, Neighbourhood::Orthogonal)
+                .filter(|&n| self.grid[n] == '.')
+        })
+    }
+
+    // Calculates single round: movement and combat of each unit. Returns false once the battle is
+    // over and one of the factions has been eradicated.
+    pub fn tick(&mut self) -> bool {
+        let _span = Stopwatch::span("tick");
+        // Order units by "reading order".
+        self.units.sort_by_key(|u| (u.position.y, u.position.x));
+        // We can't use an iterator here, as borrowchecker would complain about read-only access to
+        // self.units later inside this loop.
+        for i in 0..self.units.len() {
+            // Is this unit still alive? One at exactly 0 HP is as dead as any.
+            if self.units[i].hp <= 0 {
+                continue;
+            }
+
+            // Find all of this unit's enemies.
+            let unit_faction = self.units[i].faction;
+            let enemies: Vec<&Combatant> = self
+                .units
+                .iter()
+                .filter(|u| u.faction != unit_faction && u.hp > 0)
+                .collect();
+
+            // Are there any enemies? Maybe the bloody fight is over?
+            if enemies.is_empty() {
+                return false;
+            }
+
+            // Find all target tiles near enemies. At this point, it'll include unreachable spots
+            // and spots that are occupied by a unit/wall. No out of bounds tiles, thanks to the
+            // checks during arena parsing.
+            let mut target_tiles = vec![];
+            for enemy in &enemies {
+                target_tiles.extend(enemy.position.neighbours());
+            }
+            let mut unit_position = self.units[i].position;
+            let paths = self.paths_from(unit_position);
+            let distance = |p: Point| paths.cost(p).unwrap_or(usize::MAX);
+
+            // Pick the target: closest, and first in "reading order".
+            // Unreachable targets get distance == usize::MAX.
+            target_tiles.sort_by_key(|&p| (distance(p), p.y, p.x));
+            let target_tile = target_tiles[0];
+
+            // Move if feasible.
+            let target_distance = distance(target_tile);
+            if target_distance > 0 && target_distance < usize::MAX {
+                trace!(
+                    "Moving {} from {:?} to {:?}",
+                    unit_faction,
+                    unit_position,
+                    target_tile
+                );
+                let step = paths
+                    .first_step(target_tile)
+                    .expect("No origin to move towards.");
+                self.grid[unit_position] = '.';
+                self.grid[step] = unit_faction;
+                self.units[i].position = step;
+                unit_position = self.units[i].position;
+            }
+
+            // Check for targets around unit after the move.
+            let ap = self.units[i].ap;
+            let mut targets: Vec<_> = self
+                .units
+                .iter_mut()
+                .filter(|u| {
+                    u.faction != unit_faction
+                        && u.hp > 0
+                        && unit_position.distance_to(&u.position) == 1
+                })
+                .collect();
+
+            // Damage the weakest.
+            if !targets.is_empty() {
+                targets.sort_by_key(|u| u.hp);
+                trace!(
+                    "{} damages enemy at {:?}",
+                    unit_faction,
+                    targets[0].position
+                );
+                let target = &mut targets[0];
+                target.hp -= ap;
+                if target.hp <= 0 {
+                    self.grid[target.position] = '.';
+                }
+            }
+        }
+        // Tick the clock!
+        self.clock += 1;
+        true
+    }
+
+    // Fights until the battle is over, showing off the arena after every round (at trace level).
+    pub fn fight(&mut self) -> Result<usize, GaveUp> {
+        Driver::new()
+            .budget(Budget::unlimited().steps(ROUNDS))
+            .progress(1, |_, arena: &Arena| {
+                trace!("After {} rounds:\n{:?}", arena.clock, arena)
+            })
+            .run(self)
+    }
+
+    // Rows of the arena, each followed by the units on it: #..GEG#   G(200), E(188), G(194)
+    fn printout(&self) -> Vec<String> {
+        self.grid
+            .rows()
+            .enumerate()
+            .map(|(y, row)| {
+                let row: String = row.iter().collect();
+                let mut units: Vec<&Combatant> = self
+                    .units
+                    .iter()
+                    .filter(|u| u.position.y == y && u.hp > 0)
+                    .collect();
+                if units.is_empty() {
+                    return row;
+                }
+                units.sort_by_key(|u| u.position.x);
+                let hps: Vec<String> = units.iter().map(|u| format!("{:?}", u)).collect();
+                format!("{}   {}", row, hps.join(", "))
+            })
+            .collect()
+    }
+
+    pub fn outcome(&self) -> usize {
+        let hp_sum: usize = self
+            .units
+            .iter()
+            .filter_map(|u| if u.hp > 0 { Some(u.hp as usize) } else { None })
+            .sum();
+        hp_sum * self.clock
+    }
+}
+
+// Walls, caverns, and the two armies: elves in green, goblins in red. The fallen are already
+// gone from the grid.
+impl Render for Arena {
+    fn render(&self) -> Frame {
+        let palette = Palette::new(render::BLACK)
+            .with('#', render::DARK_GREY)
+            .with('E', render::GREEN)
+            .with('G', render::RED);
+        Frame::from_chars(&self.grid, &palette)
+    }
+}
+
+// The arena, with everyone's hit points next to their row, like in the puzzle.
+impl Watch for Arena {
+    fn scene(&self) -> Scene {
+        let picture = self.printout();
+        let alive = |faction| {
+            self.units
+                .iter()
+                .filter(|u| u.faction == faction && u.hp > 0)
+                .count()
+        };
+        Scene {
+            step: self.clock,
+            picture,
+            notes: vec![format!("{} elves, {} goblins", alive('E'), alive('G'))],
+            events: self
+                .units
+                .iter()
+                .filter(|u| u.hp <= 0)
+                .map(|u| Event {
+                    at: Some((u.position.x, u.position.y)),
+                    text: format!(
+                        "{} #{} falls at {},{}",
+                        if u.faction == 'E' { "Elf" } else { "Goblin" },
+                        u.id,
+                        u.position.x,
+                        u.position.y
+                    ),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Simulation for Arena {
+    fn step(&mut self) {
+        if !self.tick() {
+            self.done = true;
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.done
+    }
+
+    // Living units are all that changes; the grid just follows them around.
+    fn state_hash(&self) -> u64 {
+        let units: Vec<_> = self
+            .units
+            .iter()
+            .filter(|u| u.hp > 0)
+            .map(|u| (u.position, u.faction, u.hp))
+            .collect();
+        simulation::state_hash(&units)
+    }
+}
+
+// The arena the way the puzzle draws it after every round, with the hit points of the units on
+// every row next to it.
+impl fmt::Debug for Arena {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.printout().join("\n"))
+    }
+}
+
+pub fn part1(input: &str) -> Result<usize, GaveUp> {
+    let mut arena = Arena::from_str(input, 3).unwrap();
+    arena.fight()?;
+    Ok(arena.outcome())
+}
+
+pub fn part2(input: &str) -> Result<usize, GaveUp> {
+    let mut elf_ap = 4;
+    let mut attempts = Budget::unlimited().steps((MAX_ELF_AP - elf_ap + 1) as usize);
+    let mut arena = Arena::from_str(input, elf_ap).unwrap();
+    let elven_army_size = arena
+        .units
+        .iter()
+        .filter(|u| u.faction == 'E')
+        .fold(0, |acc, _| acc + 1);
+    debug!("Starting elvish army size: {}", elven_army_size);
+    loop {
+        attempts.spend()?;
+        debug!("Trying out elf_ap={}", elf_ap);
+        arena.fight()?;
+
+        let surviving_elves: Vec<_> = arena
+            .units
+            .iter()
+            .filter(|u| u.hp > 0 && u.faction == 'E')
+            .collect();
+        debug!(
+            "{} surviving elves: {:?}",
+            surviving_elves.len(),
+            surviving_elves
+        );
+        if surviving_elves.len() == elven_army_size {
+            break;
+        } else {
+            elf_ap += 1;
+            arena = Arena::from_str(input, elf_ap).unwrap();
+        }
+    }
+    Ok(arena.outcome())
+}
+
+impl Solution for Day15 {
+    // Part 2 needs to set up the arena multiple times, with different elf power, so we just hold
+    // on to the input.
+    type Data = String;
+
+    fn parse(&self, input: &str) -> Result<Self::Data, ParseError> {
+        // Set up an arena just to see whether the input makes sense.
+        Arena::from_str(input, 3)?;
+        Ok(input.to_string())
+    }
+
+    fn part1(&self, input: &Self::Data) -> Result<String, Error> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &Self::Data) -> Result<String, Error> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::Example;
+    use crate::viewer;
+
+    #[test]
+    fn test_from_str() {
+        let input = r#"#####
+                       #.G.#
+                       #..E.
+                       #####"#;
+        assert_eq!(
+            Arena::from_str(input, 3).unwrap_err(),
+            ParseError::new(3, 28, ".", "a wall around the arena")
+        );
+        let input = r#"#####
+                       #.G.#
+                       #..E#
+                       ####"#;
+        assert_eq!(
+            Arena::from_str(input, 3).unwrap_err(),
+            ParseError::new(4, 24, "####", "a row 5 tiles wide, like the first one")
+        );
+    }
+
+    #[test]
+    fn test_debug() {
+        let mut arena = Arena::from_str("#####\n#GE.#\n#..G#\n#####", 3).unwrap();
+        arena.tick();
+        assert_eq!(
+            format!("{:?}", arena),
+            "#####\n#GEG#   G(197), E(194), G(200)\n#...#\n#####"
+        );
+    }
+
+    // A unit brought down to exactly 0 HP doesn't get to hit back in the same round.
+    #[test]
+    fn test_dead_at_zero() {
+        let mut arena = Arena::from_str("#####\n#GE.#\n#####", 3).unwrap();
+        arena
+            .units
+            .iter_mut()
+            .find(|u| u.faction == 'E')
+            .unwrap()
+            .hp = 3;
+        assert!(arena.tick());
+        assert_eq!(format!("{:?}", arena), "#####\n#G..#   G(200)\n#####");
+        assert!(!arena.tick());
+    }
+
+    // Every unit that falls is reported once, however the living get shuffled around afterwards.
+    #[test]
+    fn test_deaths() {
+        let example = Example::parse("1", include_str!("../samples/15/1")).unwrap();
+        let mut arena = Arena::from_str(&example.input, 3).unwrap();
+        let deaths: Vec<(usize, String)> = viewer::record(&mut arena, 100)
+            .into_iter()
+            .flat_map(|scene| {
+                let step = scene.step;
+                scene.events.into_iter().map(move |e| (step, e.text))
+            })
+            .collect();
+        assert_eq!(
+            deaths,
+            vec![
+                (23, "Elf #2 falls at 4,2".to_string()),
+                (47, "Elf #6 falls at 5,4".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_gave_up() {
+        // Walled off from each other, the goblin and the elf never get to fight.
+        let input = r#"#######
+                       #G.#.E#
+                       #######"#;
+        assert_eq!(part1(input), Err(GaveUp { steps: ROUNDS }));
+    }
+
+    // A battle saved half way through picks up right where it left off.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_snapshot() {
+        let input = r#"#######
+                       #.G...#
+                       #...EG#
+                       #.#.#G#
+                       #..G#E#
+                       #.....#
+                       #######"#;
+        let mut arena = Arena::from_str(input, 3).unwrap();
+        for _ in 0..23 {
+            arena.tick();
+        }
+        let mut loaded: Arena =
+            crate::snapshot::from_json(&crate::snapshot::to_json(&arena).unwrap()).unwrap();
+        assert_eq!(format!("{:?}", loaded), format!("{:?}", arena));
+        loaded.fight().unwrap();
+        assert_eq!(loaded.outcome(), 27730);
+    }
+
+    // The whole battle of the first example, a frame a round.
+    #[cfg(feature = "render")]
+    #[test]
+    fn test_animate() {
+        let example = Example::parse("1", include_str!("../samples/15/1")).unwrap();
+        let mut arena = Arena::from_str(&example.input, 3).unwrap();
+        let path = std::env::temp_dir().join(format!("aoc15-test-{}.gif", std::process::id()));
+        let path = path.to_str().unwrap();
+        let frames = crate::render::Renderer::new()
+            .animate(&mut arena, 1000, path)
+            .unwrap();
+        let gif = std::fs::read(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        // The start, 47 full rounds, and the one cut short.
+        assert_eq!(frames, 49);
+        assert_eq!(&gif[..6], b"GIF89a");
+    }
+}
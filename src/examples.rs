@@ -0,0 +1,187 @@
+// Examples from the puzzle text, kept in files under samples/NN/, one example per file. A file
+// starts with a header: the answers the example should give, any puzzle parameters it needs (see
+// Params), and maybe a reason to skip it for now. A line of three dashes ends the header, and the
+// input follows, as is:
+//
+//   # The first example from the puzzle.
+//   part1: 17
+//   part2: 16
+//   safe_area_within: 32
+//   ---
+//   1, 1
+//   1, 6
+//   ...
+//
+// Lines starting with # are comments. build.rs turns every one of these files into a test, so
+// adding an example doesn't take any Rust.
+use crate::{Params, Runnable};
+use std::fs;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Example {
+    pub name: String,
+    // Expected answers for both parts; examples often only have one of them.
+    pub answers: [Option<String>; 2],
+    pub params: Params,
+    // Why the example is skipped, if it is.
+    pub ignore: Option<String>,
+    pub input: String,
+}
+
+impl Example {
+    pub fn parse(name: &str, text: &str) -> Result<Example, String> {
+        let mut example = Example {
+            name: name.to_string(),
+            ..Example::default()
+        };
+        let mut rest = text;
+        let mut n = 0;
+        loop {
+            let (line, next) = match rest.find('\n') {
+                Some(i) => (&rest[..i], &rest[i + 1..]),
+                None if rest.is_empty() => {
+                    return Err(format!("{}: expected a --- line before the input", name))
+                }
+                None => (rest, ""),
+            };
+            rest = next;
+            n += 1;
+            let line = line.trim();
+            if line == "---" {
+                break;
+            }
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line.split_once(':').ok_or_else(|| {
+                format!(
+                    "{}: line {}: expected 'name: value', found '{}'",
+                    name, n, line
+                )
+            })?;
+            let value = value.trim();
+            match key.trim() {
+                "part1" => example.answers[0] = Some(value.to_string()),
+                "part2" => example.answers[1] = Some(value.to_string()),
+                "ignore" => example.ignore = Some(value.to_string()),
+                param => example.params.set(param, value),
+            }
+        }
+        example.input = rest.to_string();
+        Ok(example)
+    }
+
+    // The file's name is the example's name.
+    pub fn load(path: &str) -> Result<Example, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        let name = path.rsplit('/').next().unwrap_or(path);
+        Example::parse(name, &text)
+    }
+
+    // Solves the example, and compares the answers with the expected ones. Only parts with an
+    // expected answer get solved; an example for one part doesn't always make sense for the
+    // other (day 1's part 2 would never finish with some of the part 1 examples).
+    pub fn check(&self, solution: &dyn Runnable) -> Result<(), String> {
+        let part = match &self.answers {
+            [Some(_), Some(_)] => None,
+            [Some(_), None] => Some(1),
+            [None, Some(_)] => Some(2),
+            [None, None] => return Err(format!("{}: no answers to check", self.name)),
+        };
+        let report = solution
+            .run_with(&self.input, part, &self.params)
+            .map_err(|e| format!("{}: {}", self.name, e))?;
+        let mut wrong = vec![];
+        for (i, (expected, answer)) in self
+            .answers
+            .iter()
+            .zip([report.part1, report.part2])
+            .enumerate()
+        {
+            if let Some(expected) = expected {
+                let answer = answer.unwrap_or_default();
+                if answer != *expected {
+                    wrong.push(format!(
+                        "part {}: expected {}, got {}",
+                        i + 1,
+                        expected,
+                        answer
+                    ));
+                }
+            }
+        }
+        if wrong.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("{}: {}", self.name, wrong.join("; ")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Error, ParseError, Solution};
+
+    // Counts lines, and, for part 2, multiplies them by a parameter.
+    struct Lines;
+
+    impl Solution for Lines {
+        type Data = (usize, usize);
+
+        fn parse(&self, input: &str) -> Result<Self::Data, ParseError> {
+            Ok((input.lines().count(), 1))
+        }
+
+        const PARAMS: &'static [&'static str] = &["times"];
+
+        fn parse_with(&self, input: &str, params: &Params) -> Result<Self::Data, Error> {
+            Ok((self.parse(input)?.0, params.get("times", 1)?))
+        }
+
+        fn part1(&self, data: &Self::Data) -> Result<String, Error> {
+            Ok(data.0.to_string())
+        }
+
+        fn part2(&self, data: &Self::Data) -> Result<String, Error> {
+            Ok((data.0 * data.1).to_string())
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let example = Example::parse("1", "# Hello!\npart2: 6\n\ntimes: 3\n---\na\nb\n").unwrap();
+        assert_eq!(example.answers, [None, Some("6".to_string())]);
+        assert_eq!(example.params.get("times", 1), Ok(3));
+        assert_eq!(example.input, "a\nb\n");
+        assert_eq!(example.ignore, None);
+        let example = Example::parse("2", "ignore: later\n---").unwrap();
+        assert_eq!(example.ignore, Some("later".to_string()));
+        assert_eq!(example.input, "");
+        assert_eq!(
+            Example::parse("3", "part1 3\n---\n").unwrap_err(),
+            "3: line 1: expected 'name: value', found 'part1 3'"
+        );
+        assert!(Example::parse("4", "part1: 3\n").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let check = |text| Example::parse("x", text).unwrap().check(&Lines);
+        assert_eq!(check("part1: 2\npart2: 2\n---\na\nb"), Ok(()));
+        assert_eq!(check("part2: 6\ntimes: 3\n---\na\nb"), Ok(()));
+        assert_eq!(
+            check("part1: 1\npart2: 1\n---\na\nb"),
+            Err("x: part 1: expected 1, got 2; part 2: expected 1, got 2".to_string())
+        );
+        assert_eq!(
+            check("part2: 6\ntimes: many\n---\na\nb"),
+            Err("x: parameter times can't be 'many'".to_string())
+        );
+        assert_eq!(
+            check("part2: 6\ntime: 3\n---\na\nb"),
+            Err("x: there's no parameter time, this day takes times".to_string())
+        );
+        assert!(check("---\na\nb").is_err());
+    }
+}
@@ -1,16 +1,499 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::convert::TryInto;
 use std::fmt;
-use std::fs::File;
+use std::fs::{self, File};
+use std::io;
 use std::io::prelude::*;
 use std::num::ParseIntError;
+use std::ops::{Index, IndexMut};
 use std::str::FromStr;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-pub fn read_file(path: &str) -> String {
+pub mod bench;
+pub mod cycles;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod examples;
+pub mod log;
+pub mod pathfinding;
+mod points;
+pub mod pool;
+pub mod render;
+pub mod rounds;
+pub mod simulation;
+#[cfg(feature = "serde")]
+pub mod snapshot;
+pub mod viewer;
+// The skeleton isn't a real day, it's only compiled for its own test. That way it won't drift
+// away from the Solution trait.
+#[cfg(test)]
+mod skel;
+
+pub use points::{Point2, Point3, Point4};
+pub use simulation::{Budget, GaveUp};
+
+pub fn read_file(path: &str) -> io::Result<String> {
     let mut input = String::new();
-    // Read the input.
-    let mut f = File::open(path).unwrap();
-    f.read_to_string(&mut input).unwrap();
-    input.to_string()
+    // Read the input; "-" stands for stdin.
+    if path == "-" {
+        io::stdin().read_to_string(&mut input)?;
+    } else {
+        File::open(path)?.read_to_string(&mut input)?;
+    }
+    Ok(input)
+}
+
+// Turns a path given by the user into a list of input files. A directory stands for all the
+// files inside it (say, inputs/07/alice and inputs/07/bob), sorted by name. Anything else is
+// taken as is.
+pub fn list_inputs(path: &str) -> io::Result<Vec<String>> {
+    if path == "-" || !fs::metadata(path)?.is_dir() {
+        return Ok(vec![path.to_string()]);
+    }
+    let mut inputs = vec![];
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            inputs.push(entry.path().to_string_lossy().into_owned());
+        }
+    }
+    if inputs.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no inputs in {}", path),
+        ));
+    }
+    inputs.sort();
+    Ok(inputs)
+}
+
+// Something in the input didn't look the way a day expected it to. Lines and columns are counted
+// from 1, columns in characters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub found: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, found: &str, expected: &str) -> ParseError {
+        ParseError {
+            line,
+            column,
+            found: found.to_string(),
+            expected: expected.to_string(),
+        }
+    }
+
+    // Blames the whole line.
+    pub fn at_line(line: usize, text: &str, expected: &str) -> ParseError {
+        ParseError::new(line, 1, text, expected)
+    }
+
+    // Blames a piece of the line. part needs to be a slice of text, as we work out the column
+    // from where it sits.
+    pub fn at_part(line: usize, text: &str, part: &str, expected: &str) -> ParseError {
+        let offset = part.as_ptr() as usize - text.as_ptr() as usize;
+        assert!(offset + part.len() <= text.len());
+        ParseError::new(line, text[..offset].chars().count() + 1, part, expected)
+    }
+
+    // Renders the error like rustc would, with the offending bit of input underlined.
+    pub fn diagnostic(&self, path: &str, input: &str) -> String {
+        let text = input.lines().nth(self.line - 1).unwrap_or("");
+        let gutter = " ".repeat(self.line.to_string().len());
+        let underline = "^".repeat(self.found.chars().count().max(1));
+        format!(
+            "error: expected {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}",
+            self.expected,
+            gutter,
+            path,
+            self.line,
+            self.column,
+            gutter,
+            self.line,
+            text,
+            gutter,
+            " ".repeat(self.column - 1),
+            underline
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.found.is_empty() {
+            write!(
+                f,
+                "line {}, column {}: expected {}",
+                self.line, self.column, self.expected
+            )
+        } else {
+            write!(
+                f,
+                "line {}, column {}: expected {}, found '{}'",
+                self.line, self.column, self.expected, self.found
+            )
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Anything that can stop a day from coming up with an answer: either the input doesn't make
+// sense, or the solver went on for too long without getting anywhere, or there's just no answer.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    Parse(ParseError),
+    GaveUp(GaveUp),
+    // A puzzle parameter that doesn't make sense; see Params.
+    Param(String),
+    // The input is fine, but the puzzle has no answer for it, like a mine with a single cart
+    // that has nothing to crash into.
+    NoAnswer(String),
+    // A picture that couldn't be saved; see render.
+    Render(String),
+    // A state that couldn't be saved as JSON; see snapshot.
+    Snapshot(String),
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Error {
+        Error::Parse(e)
+    }
+}
+
+impl From<GaveUp> for Error {
+    fn from(e: GaveUp) -> Error {
+        Error::GaveUp(e)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(e) => e.fmt(f),
+            Error::GaveUp(e) => e.fmt(f),
+            Error::Param(e) => e.fmt(f),
+            Error::NoAnswer(e) => e.fmt(f),
+            Error::Render(e) => e.fmt(f),
+            Error::Snapshot(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// Parses a piece of line, blaming it if that doesn't work out.
+pub fn parse_part<T: FromStr>(
+    line: usize,
+    text: &str,
+    part: &str,
+    expected: &str,
+) -> Result<T, ParseError> {
+    part.parse()
+        .map_err(|_| ParseError::at_part(line, text, part, expected))
+}
+
+// Lines of the input, numbered from 1. Blank lines at the start and at the end are skipped, just
+// like input.trim().lines() would, but the numbering still matches the input.
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    let last = input
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(n, _)| n)
+        .last();
+    input
+        .lines()
+        .enumerate()
+        .skip_while(|(_, l)| l.trim().is_empty())
+        .take_while(move |(n, _)| Some(*n) <= last)
+        .map(|(n, l)| (n + 1, l))
+}
+
+// All the integers in a line, whatever's around them: "position=<-3, 11>" has -3 and 11 in it. A
+// minus right before the digits makes a number negative.
+pub fn integers<T: FromStr>(n: usize, line: &str) -> Result<Vec<T>, ParseError> {
+    let bytes = line.as_bytes();
+    let mut numbers = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let negative = bytes[i] == b'-' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+        if !negative && !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        let expected = format!("a number that fits in {}", std::any::type_name::<T>());
+        numbers.push(parse_part(n, line, &line[start..i], &expected)?);
+    }
+    Ok(numbers)
+}
+
+// Same, for lines that hold exactly N integers; expected describes such a line.
+//
+//   let [x, y] = exact_integers(n, line, "a point, like 1, 6")?;
+pub fn exact_integers<T: FromStr, const N: usize>(
+    n: usize,
+    line: &str,
+    expected: &str,
+) -> Result<[T; N], ParseError> {
+    integers(n, line)?
+        .try_into()
+        .map_err(|_| ParseError::at_line(n, line, expected))
+}
+
+// Splits the input into blocks of lines, separated by blank lines. Every block comes with the
+// number of its first line, so that it's still possible to point at lines in it:
+//
+//   for (first, block) in blocks(input) {
+//       for (i, line) in block.lines().enumerate() {
+//           let n = first + i;
+pub fn blocks(input: &str) -> Vec<(usize, &str)> {
+    let mut blocks = vec![];
+    // Line number and offset of the block we're in, if any.
+    let mut start = None;
+    let mut offset = 0;
+    for (i, line) in input.split_inclusive('\n').enumerate() {
+        match (start, line.trim().is_empty()) {
+            (None, false) => start = Some((i + 1, offset)),
+            (Some((n, from)), true) => {
+                blocks.push((n, input[from..offset].trim_end()));
+                start = None;
+            }
+            _ => (),
+        }
+        offset += line.len();
+    }
+    if let Some((n, from)) = start {
+        blocks.push((n, input[from..].trim_end()));
+    }
+    blocks
+}
+
+// Splits a rule like "..#.. => #" into what's on either side of the separator, trimmed. Both
+// sides have to be there.
+pub fn rule<'a>(
+    n: usize,
+    line: &'a str,
+    separator: &str,
+    expected: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    match line.split_once(separator) {
+        Some((key, value)) if !key.trim().is_empty() && !value.trim().is_empty() => {
+            Ok((key.trim(), value.trim()))
+        }
+        _ => Err(ParseError::at_line(n, line, expected)),
+    }
+}
+
+// Puzzle parameters that aren't part of the input, like the number of workers on day 7. The
+// examples in the puzzle text tend to use other values than the real puzzle does; days use the
+// real ones when no others are given.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Params {
+    values: HashMap<String, String>,
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.values.insert(name.to_string(), value.to_string());
+    }
+
+    pub fn get<T: FromStr>(&self, name: &str, default: T) -> Result<T, Error> {
+        match self.values.get(name) {
+            None => Ok(default),
+            Some(value) => value
+                .parse()
+                .map_err(|_| Error::Param(format!("parameter {} can't be '{}'", name, value))),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    // A name the day doesn't know is most likely a typo, and quietly going with the default would
+    // give the wrong answer without anyone noticing.
+    pub fn check(&self, known: &[&str]) -> Result<(), Error> {
+        let mut unknown: Vec<&String> = self
+            .values
+            .keys()
+            .filter(|name| !known.contains(&name.as_str()))
+            .collect();
+        unknown.sort();
+        match unknown.first() {
+            None => Ok(()),
+            Some(name) if known.is_empty() => Err(Error::Param(format!(
+                "there's no parameter {}, this day doesn't take any",
+                name
+            ))),
+            Some(name) => Err(Error::Param(format!(
+                "there's no parameter {}, this day takes {}",
+                name,
+                known.join(", ")
+            ))),
+        }
+    }
+}
+
+// Every day implements this. parse() turns the raw input into whatever the day likes to work
+// with, and both parts get to look at it. Answers are strings, as that's what the puzzle page
+// wants to see anyway: some days answer with coordinates, some with words. Parts that could go on
+// forever on a bad input give up with an Error instead.
+pub trait Solution {
+    type Data;
+    fn parse(&self, input: &str) -> Result<Self::Data, ParseError>;
+    fn part1(&self, data: &Self::Data) -> Result<String, Error>;
+    fn part2(&self, data: &Self::Data) -> Result<String, Error>;
+
+    // Names of the puzzle parameters the day takes; any other name is an Error::Param.
+    const PARAMS: &'static [&'static str] = &[];
+
+    // Days with puzzle parameters pick them up here, and keep them in Data for the parts to use.
+    fn parse_with(&self, input: &str, _params: &Params) -> Result<Self::Data, Error> {
+        Ok(self.parse(input)?)
+    }
+}
+
+// Answers produced by a single run of a day.
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    // Where the time went: "parse", "part1" and "part2", plus whatever spans the day opens.
+    pub spans: Timing,
+    pub elapsed: Duration,
+}
+
+// Solution has an associated type, so we can't keep different days in a single Vec<Box<dyn
+// Solution>>. Runnable hides the Data type, and is implemented for every Solution.
+pub trait Runnable {
+    // Parse the input and solve the requested part; None means both parts.
+    fn run(&self, input: &str, part: Option<usize>) -> Result<Report, Error> {
+        self.run_with(input, part, &Params::default())
+    }
+
+    // Same, with puzzle parameters other than the real puzzle's.
+    fn run_with(&self, input: &str, part: Option<usize>, params: &Params) -> Result<Report, Error>;
+}
+
+impl<S: Solution> Runnable for S {
+    fn run_with(&self, input: &str, part: Option<usize>, params: &Params) -> Result<Report, Error> {
+        let (report, spans) = Stopwatch::record("total", || {
+            let data = {
+                let _span = Stopwatch::span("parse");
+                params.check(S::PARAMS)?;
+                self.parse_with(input, params)?
+            };
+            let mut report = Report::default();
+            if part.is_none() || part == Some(1) {
+                let _span = Stopwatch::span("part1");
+                report.part1 = Some(self.part1(&data)?);
+            }
+            if part.is_none() || part == Some(2) {
+                let _span = Stopwatch::span("part2");
+                report.part2 = Some(self.part2(&data)?);
+            }
+            Ok::<_, Error>(report)
+        });
+        let mut report = report?;
+        report.elapsed = spans.elapsed;
+        report.spans = spans;
+        Ok(report)
+    }
+}
+
+// Send and Sync, so that days can be solved on threads of their own.
+pub type Days = Vec<(usize, Box<dyn Runnable + Send + Sync>)>;
+
+// All the days we know how to solve, in order.
+pub fn days() -> Days {
+    vec![
+        (1, Box::new(day01::Day01)),
+        (2, Box::new(day02::Day02)),
+        (3, Box::new(day03::Day03)),
+        (4, Box::new(day04::Day04)),
+        (5, Box::new(day05::Day05)),
+        (6, Box::new(day06::Day06)),
+        (7, Box::new(day07::Day07)),
+        (8, Box::new(day08::Day08)),
+        (9, Box::new(day09::Day09)),
+        (10, Box::new(day10::Day10)),
+        (11, Box::new(day11::Day11)),
+        (12, Box::new(day12::Day12)),
+        (13, Box::new(day13::Day13)),
+        (14, Box::new(day14::Day14)),
+        (15, Box::new(day15::Day15)),
+    ]
+}
+
+// Known good answers, as listed in answers.txt. Every line there holds a day, a part, the input
+// file the answer is for, and the answer itself (which is the rest of the line):
+//
+//   7 2 inputs/07 1115
+//
+// An answer of ? (see UNCHECKED) is known, but can't be checked: day 10's message is a picture,
+// for a human to read. Empty lines and lines starting with # are ignored.
+#[derive(Debug, Default)]
+pub struct Answers {
+    known: HashMap<(usize, usize, String), String>,
+}
+
+impl Answers {
+    pub const UNCHECKED: &'static str = "?";
+
+    pub fn get(&self, day: usize, part: usize, input: &str) -> Option<&str> {
+        self.known
+            .get(&(day, part, input.to_string()))
+            .map(|a| a.as_str())
+    }
+}
+
+impl FromStr for Answers {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut known = HashMap::new();
+        for (n, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 4 {
+                return Err(format!("line {}: expected 'day part input answer'", n + 1));
+            }
+            let day = fields[0]
+                .parse()
+                .map_err(|_| format!("line {}: '{}' is not a day", n + 1, fields[0]))?;
+            let part = fields[1]
+                .parse()
+                .map_err(|_| format!("line {}: '{}' is not a part", n + 1, fields[1]))?;
+            known.insert((day, part, fields[2].to_string()), fields[3..].join(" "));
+        }
+        Ok(Answers { known })
+    }
 }
 
 pub struct Stopwatch {
@@ -24,13 +507,201 @@ impl Stopwatch {
         }
     }
 
-    pub fn split(self) {
-        let d = self.clock.elapsed();
-        eprintln!("Done in {}.{:06} seconds", d.as_secs(), d.subsec_micros());
+    pub fn elapsed(&self) -> Duration {
+        self.clock.elapsed()
+    }
+
+    // Starts timing a named span of code, which lasts until the returned Span is dropped:
+    //
+    //   let _span = Stopwatch::span("tick");
+    //
+    // Spans opened while another one is open end up nested inside it. They're only kept while
+    // somebody's recording (see below), otherwise they go nowhere.
+    pub fn span(name: &str) -> Span {
+        OPEN_SPANS.with(|spans| spans.borrow_mut().push(Timing::new(name)));
+        Span {
+            stopwatch: Stopwatch::start(),
+        }
+    }
+
+    // Runs f inside a span, and hands back the timings of it and everything nested inside.
+    pub fn record<R, F: FnOnce() -> R>(name: &str, f: F) -> (R, Timing) {
+        let span = Stopwatch::span(name);
+        let result = f();
+        (result, span.close())
+    }
+}
+
+// Time spent in a named span of code, along with the spans nested inside it. Spans with the same
+// name and parent are added up, so a span inside a loop shows up once, with the number of calls.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Timing {
+    pub name: String,
+    pub calls: usize,
+    pub elapsed: Duration,
+    pub children: Vec<Timing>,
+}
+
+impl Timing {
+    fn new(name: &str) -> Timing {
+        Timing {
+            name: name.to_string(),
+            ..Timing::default()
+        }
+    }
+
+    fn add_child(&mut self, child: Timing) {
+        match self.children.iter_mut().find(|c| c.name == child.name) {
+            Some(c) => {
+                c.calls += child.calls;
+                c.elapsed += child.elapsed;
+                for grandchild in child.children {
+                    c.add_child(grandchild);
+                }
+            }
+            None => self.children.push(child),
+        }
+    }
+
+    // Looks up a nested span by its path, like "part1/tick".
+    pub fn get(&self, path: &str) -> Option<&Timing> {
+        path.split('/').try_fold(self, |timing, name| {
+            timing.children.iter().find(|c| c.name == name)
+        })
+    }
+
+    // Time spent in a nested span; zero if it never ran.
+    pub fn time(&self, path: &str) -> Duration {
+        self.get(path).map(|t| t.elapsed).unwrap_or_default()
+    }
+
+    // All spans in the tree, depth first, along with their paths ("total/part1/tick").
+    pub fn flatten(&self) -> Vec<(String, &Timing)> {
+        let mut all = vec![(self.name.clone(), self)];
+        for child in &self.children {
+            for (path, t) in child.flatten() {
+                all.push((format!("{}/{}", self.name, path), t));
+            }
+        }
+        all
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("span,calls,elapsed_ns");
+        for (path, t) in self.flatten() {
+            csv.push_str(&format!("\n{},{},{}", path, t.calls, t.elapsed.as_nanos()));
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> String {
+        let children: Vec<String> = self.children.iter().map(|c| c.to_json()).collect();
+        format!(
+            "{{\"name\": {}, \"calls\": {}, \"elapsed_ns\": {}, \"children\": [{}]}}",
+            bench::json_string(&self.name),
+            self.calls,
+            self.elapsed.as_nanos(),
+            children.join(", ")
+        )
+    }
+}
+
+thread_local! {
+    // Spans currently open on this thread, innermost last.
+    static OPEN_SPANS: RefCell<Vec<Timing>> = const { RefCell::new(Vec::new()) };
+}
+
+// A span that's still being timed. Spans need to be closed in the reverse order they were opened
+// in, which is what happens anyway if they're just left to go out of scope.
+pub struct Span {
+    stopwatch: Stopwatch,
+}
+
+impl Span {
+    fn finish(&self) -> Timing {
+        let mut timing = OPEN_SPANS.with(|spans| spans.borrow_mut().pop().unwrap());
+        timing.calls = 1;
+        timing.elapsed = self.stopwatch.elapsed();
+        timing
+    }
+
+    // Closes the span, handing its timing over to the caller instead of the enclosing span.
+    fn close(self) -> Timing {
+        let timing = self.finish();
+        std::mem::forget(self);
+        timing
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let timing = self.finish();
+        OPEN_SPANS.with(|spans| {
+            if let Some(parent) = spans.borrow_mut().last_mut() {
+                parent.add_child(timing);
+            }
+        });
+    }
+}
+
+// Which of the surrounding points count as neighbours: the four sharing an edge, the four sharing
+// just a corner, or all eight of them.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Neighbourhood {
+    Orthogonal,
+    Diagonal,
+    Moore,
+}
+
+impl Neighbourhood {
+    // Offsets of the neighbours, in reading order.
+    fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Neighbourhood::Orthogonal => &[(0, -1), (-1, 0), (1, 0), (0, 1)],
+            Neighbourhood::Diagonal => &[(-1, -1), (1, -1), (-1, 1), (1, 1)],
+            Neighbourhood::Moore => &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+        }
+    }
+}
+
+// Neighbours of a point, always in reading order: top to bottom, and left to right within a row.
+// Points that would need a negative coordinate are skipped, and so are the ones outside of the
+// grid, when they come from Grid::neighbours. Doesn't allocate, so it's fine to use in hot loops.
+#[derive(Clone, Debug)]
+pub struct Neighbours {
+    centre: Point,
+    offsets: std::slice::Iter<'static, (isize, isize)>,
+    // Width and height of the area the neighbours need to fit in.
+    bounds: (usize, usize),
+}
+
+impl Iterator for Neighbours {
+    type Item = Point;
+    fn next(&mut self) -> Option<Point> {
+        for &(dx, dy) in self.offsets.by_ref() {
+            let x = self.centre.x.checked_add_signed(dx);
+            let y = self.centre.y.checked_add_signed(dy);
+            if let (Some(x), Some(y)) = (x, y) {
+                if x < self.bounds.0 && y < self.bounds.1 {
+                    return Some(Point::new(x, y));
+                }
+            }
+        }
+        None
     }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point {
     pub x: usize,
     pub y: usize,
@@ -50,7 +721,8 @@ impl FromStr for Point {
             .split(',')
             .collect();
         let x = parts[0].trim().parse::<usize>()?;
-        let y = parts[1].trim().parse::<usize>()?;
+        // A missing y fails to parse, rather than panics.
+        let y = parts.get(1).unwrap_or(&"").trim().parse::<usize>()?;
         Ok(Point { x, y })
     }
 }
@@ -60,13 +732,17 @@ impl Point {
         Point { x, y }
     }
 
-    pub fn neighbours(&self) -> Vec<Point> {
-        vec![
-            Point::new(self.x - 1, self.y),
-            Point::new(self.x + 1, self.y),
-            Point::new(self.x, self.y - 1),
-            Point::new(self.x, self.y + 1),
-        ]
+    // Orthogonal neighbours: up, left, right and down.
+    pub fn neighbours(&self) -> Neighbours {
+        self.neighbours_in(Neighbourhood::Orthogonal)
+    }
+
+    pub fn neighbours_in(&self, kind: Neighbourhood) -> Neighbours {
+        Neighbours {
+            centre: *self,
+            offsets: kind.offsets().iter(),
+            bounds: (usize::MAX, usize::MAX),
+        }
     }
 
     pub fn distance_to(&self, other: &Point) -> usize {
@@ -74,3 +750,541 @@ impl Point {
             as usize
     }
 }
+
+// A rectangular grid of things, indexed with Points: x goes right, y goes down. Cells are kept in
+// a single Vec, row after row.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "RawGrid<T>")
+)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+// A grid as it comes out of a snapshot, before anyone has checked that the cells fill it exactly.
+// Snapshots get edited by hand, and a cell too few would only blow up on the first index into it.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawGrid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> std::convert::TryFrom<RawGrid<T>> for Grid<T> {
+    type Error = String;
+
+    fn try_from(raw: RawGrid<T>) -> Result<Grid<T>, String> {
+        if raw.width.checked_mul(raw.height) != Some(raw.cells.len()) {
+            return Err(format!(
+                "a {}x{} grid can't have {} cells",
+                raw.width,
+                raw.height,
+                raw.cells.len()
+            ));
+        }
+        Ok(Grid {
+            width: raw.width,
+            height: raw.height,
+            cells: raw.cells,
+        })
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    // Works out every cell from its position.
+    pub fn from_fn<F: FnMut(Point) -> T>(width: usize, height: usize, f: F) -> Grid<T> {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Point::new(x, y)))
+            .map(f)
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    // None if the rows aren't all the same width.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Grid<T>> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |r| r.len());
+        if rows.iter().any(|r| r.len() != width) {
+            return None;
+        }
+        Some(Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    // Reads a map drawn with characters, one row per line; tile() says what each character stands
+    // for, or None if it doesn't belong on the map. Blank lines around the map are skipped, but
+    // nothing is trimmed off the lines themselves.
+    pub fn parse_map<F>(input: &str, expected: &str, tile: F) -> Result<Grid<T>, ParseError>
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        let mut rows = vec![];
+        let mut width = None;
+        for (n, line) in numbered_lines(input) {
+            let mut row = vec![];
+            for (i, c) in line.char_indices() {
+                match tile(c) {
+                    Some(t) => row.push(t),
+                    None => {
+                        return Err(ParseError::at_part(
+                            n,
+                            line,
+                            &line[i..i + c.len_utf8()],
+                            expected,
+                        ))
+                    }
+                }
+            }
+            match width {
+                None => width = Some(row.len()),
+                Some(w) if w != row.len() => {
+                    return Err(ParseError::at_line(
+                        n,
+                        line,
+                        &format!("a row {} tiles wide, like the first one", w),
+                    ))
+                }
+                _ => (),
+            }
+            rows.push(row);
+        }
+        if rows.is_empty() {
+            return Err(ParseError::new(1, 1, "", expected));
+        }
+        Ok(Grid::from_rows(rows).unwrap())
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x < self.width && p.y < self.height
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        if self.contains(p) {
+            Some(&self.cells[p.y * self.width + p.x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        if self.contains(p) {
+            Some(&mut self.cells[p.y * self.width + p.x])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks() doesn't like zero, and a grid without columns has no rows to speak of.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    // Neighbours of p that are on the grid.
+    pub fn neighbours(&self, p: Point, kind: Neighbourhood) -> Neighbours {
+        Neighbours {
+            bounds: (self.width, self.height),
+            ..p.neighbours_in(kind)
+        }
+    }
+
+    // Every point of the grid, in reading order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    // Every cell along with its position, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    // Cells of the rectangle with top left corner at given point; whatever sticks out of the grid
+    // is cut off.
+    pub fn region(
+        &self,
+        corner: Point,
+        width: usize,
+        height: usize,
+    ) -> impl Iterator<Item = (Point, &T)> {
+        let (xs, ys) = self.clip(corner, width, height);
+        ys.flat_map(move |y| {
+            let xs = xs.clone();
+            self.row(y)[xs.clone()]
+                .iter()
+                .zip(xs)
+                .map(move |(c, x)| (Point::new(x, y), c))
+        })
+    }
+
+    pub fn region_mut(
+        &mut self,
+        corner: Point,
+        width: usize,
+        height: usize,
+    ) -> impl Iterator<Item = (Point, &mut T)> {
+        let (xs, ys) = self.clip(corner, width, height);
+        self.cells
+            .chunks_mut(self.width.max(1))
+            .enumerate()
+            .skip(ys.start)
+            .take(ys.len())
+            .flat_map(move |(y, row)| {
+                let xs = xs.clone();
+                row[xs.clone()]
+                    .iter_mut()
+                    .zip(xs)
+                    .map(move |(c, x)| (Point::new(x, y), c))
+            })
+    }
+
+    fn clip(
+        &self,
+        corner: Point,
+        width: usize,
+        height: usize,
+    ) -> (std::ops::Range<usize>, std::ops::Range<usize>) {
+        let x = corner.x.min(self.width);
+        let y = corner.y.min(self.height);
+        (
+            x..corner.x.saturating_add(width).min(self.width).max(x),
+            y..corner.y.saturating_add(height).min(self.height).max(y),
+        )
+    }
+}
+
+// Indexing outside of the grid panics, just like it would with a Vec.
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+    fn index(&self, p: Point) -> &T {
+        assert!(self.contains(p), "{:?} is outside of the grid", p);
+        &self.cells[p.y * self.width + p.x]
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        assert!(self.contains(p), "{:?} is outside of the grid", p);
+        &mut self.cells[p.y * self.width + p.x]
+    }
+}
+
+// Draws the grid a row per line, cells right next to each other; handy for Grid<char>.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// One test for every example under samples/; build.rs writes them.
+#[cfg(test)]
+mod samples {
+    use crate::examples::Example;
+
+    fn check(day: usize, path: &str) {
+        let days = crate::days();
+        let (_, solution) = days
+            .iter()
+            .find(|(d, _)| *d == day)
+            .unwrap_or_else(|| panic!("Day {} isn't solved (yet?).", day));
+        let example = Example::load(path).unwrap_or_else(|e| panic!("{}", e));
+        if let Err(e) = example.check(solution.as_ref()) {
+            panic!("Day {}: {}", day, e);
+        }
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/samples.rs"));
+}
+
+// One test for every file of printouts under rounds/; build.rs writes them too.
+#[cfg(test)]
+mod round_by_round {
+    use crate::examples::Example;
+    use crate::{day12, day15, rounds};
+
+    // More than any example takes, for the simulations that are never done.
+    const MAX_ROUNDS: usize = 100;
+
+    fn check(day: usize, sample: &str, path: &str) {
+        let input = Example::load(sample)
+            .unwrap_or_else(|e| panic!("{}", e))
+            .input;
+        let result = match day {
+            // The pots the puzzle shows, from -3 to 35.
+            12 => rounds::check(
+                &mut day12::parse_input(&input).unwrap(),
+                20,
+                |pots| pots.row(-3, 35),
+                path,
+            ),
+            15 => rounds::check(
+                &mut day15::Arena::from_str(&input, 3).unwrap(),
+                MAX_ROUNDS,
+                |arena| format!("{:?}", arena),
+                path,
+            ),
+            _ => panic!("Day {} doesn't print its rounds.", day),
+        };
+        if let Err(e) = result {
+            panic!("Day {}: {}", day, e);
+        }
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/rounds.rs"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integers() {
+        let line = "position=<-3, 11> velocity=< 1,-2>";
+        assert_eq!(integers(1, line), Ok(vec![-3, 11, 1, -2]));
+        assert_eq!(integers::<i32>(1, "no numbers - here"), Ok(vec![]));
+        assert_eq!(integers(1, "#1 @ 1,3: 4x4"), Ok(vec![1u8, 1, 3, 4, 4]));
+        assert_eq!(
+            integers::<u8>(3, "1, -2").unwrap_err(),
+            ParseError::new(3, 4, "-2", "a number that fits in u8")
+        );
+        assert_eq!(
+            exact_integers(1, "10 players; 1618 points", "a game"),
+            Ok([10, 1618])
+        );
+        assert_eq!(
+            exact_integers::<i32, 2>(1, "10 players", "a game").unwrap_err(),
+            ParseError::at_line(1, "10 players", "a game")
+        );
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = "\nstate: #.#\n\n\n..# => #\n#.. => .\n\n";
+        assert_eq!(
+            blocks(input),
+            vec![(2, "state: #.#"), (5, "..# => #\n#.. => .")]
+        );
+        assert_eq!(blocks("one\r\n  \r\ntwo"), vec![(1, "one"), (3, "two")]);
+        assert!(blocks(" \n\n").is_empty());
+    }
+
+    #[test]
+    fn test_rule() {
+        assert_eq!(rule(1, "..#.. => #", "=>", "a rule"), Ok(("..#..", "#")));
+        assert_eq!(rule(1, "state: #.#", ":", "a rule"), Ok(("state", "#.#")));
+        assert_eq!(
+            rule(7, "..#.. =>", "=>", "a rule").unwrap_err(),
+            ParseError::at_line(7, "..#.. =>", "a rule")
+        );
+    }
+
+    #[test]
+    fn test_answers() {
+        let answers: Answers = r#"
+# day part input answer
+1 1 inputs/01 590
+2 2 inputs/02   hhvsdkatysmiqjxunezgwcdpr
+"#
+        .parse()
+        .unwrap();
+        assert_eq!(answers.get(1, 1, "inputs/01"), Some("590"));
+        assert_eq!(
+            answers.get(2, 2, "inputs/02"),
+            Some("hhvsdkatysmiqjxunezgwcdpr")
+        );
+        assert_eq!(answers.get(1, 2, "inputs/01"), None);
+        let answers: Answers = "10 1 inputs/10 ?".parse().unwrap();
+        assert_eq!(answers.get(10, 1, "inputs/10"), Some(Answers::UNCHECKED));
+        assert!("1 1 inputs/01".parse::<Answers>().is_err());
+        assert!("one 1 inputs/01 590".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let line = "#1 @ 1,x: 4x4";
+        let e = parse_part::<usize>(3, line, &line[7..8], "a number").unwrap_err();
+        assert_eq!(e, ParseError::new(3, 8, "x", "a number"));
+        assert_eq!(
+            e.to_string(),
+            "line 3, column 8: expected a number, found 'x'"
+        );
+        assert_eq!(
+            e.diagnostic("inputs/03", "\n\n#1 @ 1,x: 4x4\n"),
+            r#"error: expected a number
+ --> inputs/03:3:8
+  |
+3 | #1 @ 1,x: 4x4
+  |        ^"#
+        );
+    }
+
+    #[test]
+    fn test_numbered_lines() {
+        let lines: Vec<_> = numbered_lines("\n\na\n\nb\n  \n").collect();
+        assert_eq!(lines, vec![(3, "a"), (4, ""), (5, "b")]);
+        assert_eq!(numbered_lines("\n \n").count(), 0);
+    }
+
+    #[test]
+    fn test_spans() {
+        let ((), total) = Stopwatch::record("total", || {
+            let _part1 = Stopwatch::span("part1");
+            for _ in 0..3 {
+                let _tick = Stopwatch::span("tick");
+                let _move = Stopwatch::span("move");
+            }
+        });
+        // Nobody's recording this one.
+        drop(Stopwatch::span("lost"));
+        assert_eq!(total.calls, 1);
+        assert_eq!(total.get("part1/tick").unwrap().calls, 3);
+        assert_eq!(total.get("part1/tick/move").unwrap().calls, 3);
+        assert!(total.time("part1") >= total.time("part1/tick"));
+        assert_eq!(total.get("part2"), None);
+        let paths: Vec<_> = total.flatten().into_iter().map(|(p, _)| p).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "total",
+                "total/part1",
+                "total/part1/tick",
+                "total/part1/tick/move"
+            ]
+        );
+        assert!(total
+            .to_json()
+            .starts_with("{\"name\": \"total\", \"calls\": 1, \"elapsed_ns\": "));
+        OPEN_SPANS.with(|spans| assert!(spans.borrow().is_empty()));
+    }
+
+    #[test]
+    fn test_grid() {
+        let mut grid = Grid::parse_map("\n#..\n.#.\n", "a wall or floor", |c| match c {
+            '#' | '.' => Some(c),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(1, 1)], '#');
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.row(0), &['#', '.', '.']);
+        assert_eq!(grid.column(0).collect::<String>(), "#.");
+        for (_, c) in grid.region_mut(Point::new(1, 0), 5, 5) {
+            *c = 'x';
+        }
+        assert_eq!(grid.to_string(), "#xx\n.xx\n");
+        let region: Vec<_> = grid.region(Point::new(2, 1), 2, 2).collect();
+        assert_eq!(region, vec![(Point::new(2, 1), &'x')]);
+        assert_eq!(grid.region(Point::new(5, 5), 2, 2).count(), 0);
+        assert_eq!(grid.region(Point::new(1, 1), usize::MAX, 1).count(), 2);
+        assert_eq!(grid.region(Point::new(1, 0), 1, usize::MAX).count(), 2);
+        assert_eq!(grid.iter().filter(|(_, &c)| c == 'x').count(), 4);
+
+        assert_eq!(
+            Grid::parse_map("#.\n.x", "a wall or floor", |c| Some(c)
+                .filter(|&c| c != 'x'))
+            .unwrap_err(),
+            ParseError::new(2, 2, "x", "a wall or floor")
+        );
+        assert_eq!(
+            Grid::parse_map("#.\n...", "a wall or floor", Some).unwrap_err(),
+            ParseError::new(2, 1, "...", "a row 2 tiles wide, like the first one")
+        );
+        assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3]]), None);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let p = |x, y| Point::new(x, y);
+        assert_eq!(
+            p(1, 1).neighbours().collect::<Vec<_>>(),
+            vec![p(1, 0), p(0, 1), p(2, 1), p(1, 2)]
+        );
+        assert_eq!(
+            p(0, 0)
+                .neighbours_in(Neighbourhood::Moore)
+                .collect::<Vec<_>>(),
+            vec![p(1, 0), p(0, 1), p(1, 1)]
+        );
+        assert_eq!(
+            p(1, 1)
+                .neighbours_in(Neighbourhood::Diagonal)
+                .collect::<Vec<_>>(),
+            vec![p(0, 0), p(2, 0), p(0, 2), p(2, 2)]
+        );
+        let grid = Grid::new(2, 2, ());
+        assert_eq!(
+            grid.neighbours(p(1, 1), Neighbourhood::Moore)
+                .collect::<Vec<_>>(),
+            vec![p(0, 0), p(1, 0), p(0, 1)]
+        );
+        assert_eq!(
+            Point::new(3, 3).neighbours_in(Neighbourhood::Moore).count(),
+            8
+        );
+    }
+
+    #[test]
+    fn test_list_inputs() {
+        assert_eq!(list_inputs("-").unwrap(), vec!["-"]);
+        assert_eq!(list_inputs("inputs/01").unwrap(), vec!["inputs/01"]);
+        assert!(list_inputs("inputs/00").is_err());
+
+        let dir = std::env::temp_dir().join(format!("aoc-list-inputs-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("bob"), "2").unwrap();
+        fs::write(dir.join("alice"), "1").unwrap();
+        let inputs = list_inputs(dir.to_str().unwrap()).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        let names: Vec<_> = inputs
+            .iter()
+            .map(|i| i.rsplit('/').next().unwrap())
+            .collect();
+        assert_eq!(names, vec!["alice", "bob"]);
+    }
+}
@@ -0,0 +1,197 @@
+// Logging, for whatever the days have to say on the side: what they're up to, or all the gory
+// details of it. Every message has a level, and comes from somewhere: the module it was logged
+// from, so "day15" for anything Arena says. What gets shown is up to a filter such as
+// "info,day15=trace" (see Filter), which the aoc binary takes from --log or AOC_LOG.
+//
+//   debug!("Trying out elf_ap={}", elf_ap);
+//
+// Messages go to stderr, so they don't get mixed up with the answers.
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::RwLock;
+
+// From the things you always want to hear about, to the things you hardly ever do.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Info,
+    Debug,
+    Trace,
+}
+
+const LEVELS: [Level; 4] = [Level::Error, Level::Info, Level::Debug, Level::Trace];
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Level, String> {
+        LEVELS
+            .iter()
+            .find(|l| l.to_string().eq_ignore_ascii_case(s))
+            .cloned()
+            .ok_or_else(|| format!("'{}' is not a log level", s))
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Level::Error => "ERROR",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// Which messages to show: anything up to some level, except where a module says otherwise.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Filter {
+    level: Level,
+    targets: Vec<(String, Level)>,
+}
+
+impl Default for Filter {
+    fn default() -> Filter {
+        Filter {
+            level: Level::Info,
+            targets: vec![],
+        }
+    }
+}
+
+impl Filter {
+    // Most detailed level shown for the target.
+    pub fn level(&self, target: &str) -> Level {
+        self.targets
+            .iter()
+            .find(|(t, _)| t == target)
+            .map_or(self.level, |&(_, level)| level)
+    }
+
+    // Most detailed level shown for anything at all.
+    fn max_level(&self) -> Level {
+        self.targets
+            .iter()
+            .map(|&(_, level)| level)
+            .fold(self.level, Level::max)
+    }
+}
+
+// Comma separated: a bare level is for everything, "target=level" for a single module. Days can
+// go by their number, so "debug,15=trace" is the same as "debug,day15=trace".
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Filter, String> {
+        let mut filter = Filter::default();
+        for part in s.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+            match part.split_once('=') {
+                None => filter.level = part.parse()?,
+                Some((target, level)) => {
+                    let target = match target.trim().parse::<usize>() {
+                        Ok(day) => format!("day{:02}", day),
+                        Err(_) => target.trim().to_string(),
+                    };
+                    filter.targets.retain(|(t, _)| *t != target);
+                    filter.targets.push((target, level.trim().parse()?));
+                }
+            }
+        }
+        Ok(filter)
+    }
+}
+
+static FILTER: RwLock<Option<Filter>> = RwLock::new(None);
+// The filter's max_level(), so that most messages that aren't shown don't even need the lock.
+static MAX_LEVEL: AtomicUsize = AtomicUsize::new(Level::Info as usize);
+
+pub fn set_filter(filter: Filter) {
+    MAX_LEVEL.store(filter.max_level() as usize, Ordering::Relaxed);
+    *FILTER.write().unwrap() = Some(filter);
+}
+
+pub fn enabled(level: Level, target: &str) -> bool {
+    if level as usize > MAX_LEVEL.load(Ordering::Relaxed) {
+        return false;
+    }
+    match &*FILTER.read().unwrap() {
+        Some(filter) => level <= filter.level(target),
+        None => level <= Level::Info,
+    }
+}
+
+// What a module path is known as: the last bit of it, "day15" for "aoc::day15".
+pub fn target(module_path: &str) -> &str {
+    module_path.rsplit("::").next().unwrap_or(module_path)
+}
+
+// What the macros below end up calling, once they know the message is wanted.
+pub fn write(level: Level, target: &str, message: fmt::Arguments) {
+    eprintln!("[{} {}] {}", level, target, message);
+}
+
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {{
+        let target = $crate::log::target(module_path!());
+        if $crate::log::enabled($level, target) {
+            $crate::log::write($level, target, format_args!($($arg)+));
+        }
+    }};
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Error, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter() {
+        let filter: Filter = "debug, 15=trace,day09=error".parse().unwrap();
+        assert_eq!(filter.level("day15"), Level::Trace);
+        assert_eq!(filter.level("day09"), Level::Error);
+        assert_eq!(filter.level("day01"), Level::Debug);
+        assert_eq!(filter.max_level(), Level::Trace);
+        assert_eq!("".parse(), Ok(Filter::default()));
+        assert_eq!(
+            "day15=trace,day15=error"
+                .parse::<Filter>()
+                .unwrap()
+                .level("day15"),
+            Level::Error
+        );
+        assert_eq!(
+            "loud".parse::<Filter>(),
+            Err("'loud' is not a log level".to_string())
+        );
+        assert!("day15=".parse::<Filter>().is_err());
+    }
+
+    #[test]
+    fn test_target() {
+        assert_eq!(target("aoc::day15"), "day15");
+        assert_eq!(target("aoc"), "aoc");
+    }
+}
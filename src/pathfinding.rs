@@ -0,0 +1,319 @@
+// Shortest paths, for anything that looks like a graph: a Grid, or something made up on the fly.
+// All we need is a function giving the neighbours of a node (plus the cost of getting there, for
+// Dijkstra and A*).
+//
+// Ties between equally short paths go to the neighbour that was handed out first. So, if the
+// neighbour function goes in reading order (like Grid::neighbours does), the path we pick goes in
+// reading order too: out of all the shortest paths, the first step is the first one in reading
+// order, and so on.
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+use std::ops::Add;
+
+// The default hasher guards against malicious input, which puzzles aren't, and it's slow at it:
+// searches spend most of their time hashing nodes. This is the one rustc uses (FxHash).
+#[derive(Default)]
+struct FastHasher(u64);
+
+impl Hasher for FastHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.write_u64(b as u64);
+        }
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.0 = (self.0.rotate_left(5) ^ n).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.write_u64(n as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+type FastMap<K, V> = HashMap<K, V, BuildHasherDefault<FastHasher>>;
+
+// Whatever a search has found out: how much it costs to reach every node it got to, and where it
+// got to each of them from.
+#[derive(Clone, Debug)]
+pub struct Paths<N, C> {
+    start: N,
+    costs: FastMap<N, C>,
+    parents: FastMap<N, N>,
+}
+
+impl<N: Copy + Eq + Hash, C: Copy> Paths<N, C> {
+    fn new(start: N, zero: C) -> Paths<N, C> {
+        let mut costs = FastMap::default();
+        costs.insert(start, zero);
+        Paths {
+            start,
+            costs,
+            parents: FastMap::default(),
+        }
+    }
+
+    pub fn start(&self) -> N {
+        self.start
+    }
+
+    // None if the node can't be reached.
+    pub fn cost(&self, node: N) -> Option<C> {
+        self.costs.get(&node).cloned()
+    }
+
+    // All the nodes that can be reached, with their costs; in no particular order.
+    pub fn reached(&self) -> impl Iterator<Item = (N, C)> + '_ {
+        self.costs.iter().map(|(&n, &c)| (n, c))
+    }
+
+    // The whole way from the start to the goal, both included.
+    pub fn path_to(&self, goal: N) -> Option<Vec<N>> {
+        self.costs.get(&goal)?;
+        let mut path = vec![goal];
+        let mut node = goal;
+        while let Some(&parent) = self.parents.get(&node) {
+            path.push(parent);
+            node = parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    // Where to go from the start, to get to the goal. None if it's unreachable, or if we're
+    // already there.
+    pub fn first_step(&self, goal: N) -> Option<N> {
+        let mut node = goal;
+        loop {
+            match self.parents.get(&node) {
+                Some(&parent) if parent == self.start => return Some(node),
+                Some(&parent) => node = parent,
+                None => return None,
+            }
+        }
+    }
+}
+
+// Breadth first search, for when every step costs the same. Visits everything reachable from
+// start; the cost of a node is the number of steps it takes to get there.
+pub fn bfs<N, F, I>(start: N, mut neighbours: F) -> Paths<N, usize>
+where
+    N: Copy + Eq + Hash,
+    F: FnMut(N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths::new(start, 0);
+    let mut queue = VecDeque::new();
+    queue.push_back((start, 0));
+    while let Some((node, cost)) = queue.pop_front() {
+        for next in neighbours(node) {
+            if paths.costs.contains_key(&next) {
+                continue;
+            }
+            paths.costs.insert(next, cost + 1);
+            paths.parents.insert(next, node);
+            queue.push_back((next, cost + 1));
+        }
+    }
+    paths
+}
+
+// Dijkstra's algorithm: neighbours come with the cost of getting to them, which can't be negative.
+// Visits everything reachable from start.
+pub fn dijkstra<N, C, F, I>(start: N, neighbours: F) -> Paths<N, C>
+where
+    N: Copy + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    F: FnMut(N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    search(start, neighbours, |_| C::default(), |_| false).0
+}
+
+// A*: like Dijkstra, but heads towards the goal, guided by the heuristic. The heuristic guesses
+// the cost from a node to the goal, and mustn't ever guess too high (Manhattan distance on a
+// grid is a good one). Returns the path from start to the goal, and its cost.
+pub fn astar<N, C, F, I, H, G>(
+    start: N,
+    neighbours: F,
+    heuristic: H,
+    is_goal: G,
+) -> Option<(Vec<N>, C)>
+where
+    N: Copy + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    F: FnMut(N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: Fn(N) -> C,
+    G: Fn(N) -> bool,
+{
+    let (paths, goal) = search(start, neighbours, heuristic, is_goal);
+    let goal = goal?;
+    Some((paths.path_to(goal)?, paths.cost(goal)?))
+}
+
+// A node waiting in the queue. BinaryHeap hands out the biggest element first, so the ordering
+// is turned around: the cheapest node wins, and of those, the one queued first.
+struct Queued<N, C> {
+    priority: C,
+    order: usize,
+    node: N,
+    cost: C,
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&other.priority, other.order).cmp(&(&self.priority, self.order))
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+// Both Dijkstra and A*; Dijkstra is just A* that has no idea where the goal is. Stops at the first
+// node that is_goal() likes, and returns it.
+fn search<N, C, F, I, H, G>(
+    start: N,
+    mut neighbours: F,
+    heuristic: H,
+    is_goal: G,
+) -> (Paths<N, C>, Option<N>)
+where
+    N: Copy + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    F: FnMut(N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: Fn(N) -> C,
+    G: Fn(N) -> bool,
+{
+    let mut paths = Paths::new(start, C::default());
+    let mut queue = BinaryHeap::new();
+    let mut order = 0;
+    queue.push(Queued {
+        priority: heuristic(start),
+        order,
+        node: start,
+        cost: C::default(),
+    });
+    while let Some(Queued { node, cost, .. }) = queue.pop() {
+        // Skip nodes we've found a cheaper way to since they were queued.
+        if cost > paths.costs[&node] {
+            continue;
+        }
+        if is_goal(node) {
+            return (paths, Some(node));
+        }
+        for (next, step) in neighbours(node) {
+            let next_cost = cost + step;
+            if paths.costs.get(&next).is_some_and(|&c| c <= next_cost) {
+                continue;
+            }
+            paths.costs.insert(next, next_cost);
+            paths.parents.insert(next, node);
+            order += 1;
+            queue.push(Queued {
+                priority: next_cost + heuristic(next),
+                order,
+                node: next,
+                cost: next_cost,
+            });
+        }
+    }
+    (paths, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Grid, Neighbourhood, Point};
+
+    const MAZE: &str = r#"
+#######
+#S....#
+#.###.#
+#.....#
+###.#.#
+#.....#
+#######
+"#;
+
+    fn maze() -> Grid<char> {
+        Grid::parse_map(MAZE, "a maze", Some).unwrap()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let maze = maze();
+        let paths = bfs(Point::new(1, 1), |p| {
+            maze.neighbours(p, Neighbourhood::Orthogonal)
+                .filter(|&n| maze[n] != '#')
+        });
+        assert_eq!(paths.cost(Point::new(5, 5)), Some(8));
+        assert_eq!(paths.cost(Point::new(0, 0)), None);
+        assert_eq!(paths.reached().count(), 19);
+        assert_eq!(paths.first_step(Point::new(3, 3)), Some(Point::new(1, 2)));
+        // Both ways around the block are equally long; right goes before down in reading order.
+        assert_eq!(paths.first_step(Point::new(5, 3)), Some(Point::new(2, 1)));
+        assert_eq!(paths.first_step(Point::new(1, 2)), Some(Point::new(1, 2)));
+        assert_eq!(paths.first_step(Point::new(1, 1)), None);
+        assert_eq!(
+            paths.path_to(Point::new(1, 3)),
+            Some(vec![Point::new(1, 1), Point::new(1, 2), Point::new(1, 3)])
+        );
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // Going up by one costs 1, doubling costs 3; cheapest way from 1 to 10?
+        let paths = dijkstra(1u32, |n| {
+            let mut next = vec![(n + 1, 1)];
+            if n * 2 <= 20 {
+                next.push((n * 2, 3));
+            }
+            next.into_iter().filter(|&(n, _)| n <= 20)
+        });
+        assert_eq!(paths.cost(10), Some(7));
+        assert_eq!(paths.path_to(10), Some(vec![1, 2, 3, 4, 5, 10]));
+        assert_eq!(paths.cost(21), None);
+    }
+
+    #[test]
+    fn test_astar() {
+        let maze = maze();
+        let goal = Point::new(5, 5);
+        let (path, cost) = astar(
+            Point::new(1, 1),
+            |p| {
+                maze.neighbours(p, Neighbourhood::Orthogonal)
+                    .filter(|&n| maze[n] != '#')
+                    .map(|n| (n, 1))
+            },
+            |p| p.distance_to(&goal),
+            |p| p == goal,
+        )
+        .unwrap();
+        assert_eq!(cost, 8);
+        assert_eq!(path.len(), 9);
+        assert_eq!(path[1], Point::new(2, 1));
+        assert_eq!(
+            astar(0, |n: i32| (n < 10).then(|| (n + 1, 1)), |_| 0, |n| n == -1).map(|(_, c)| c),
+            None::<i32>
+        );
+    }
+}
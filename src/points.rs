@@ -0,0 +1,193 @@
+// Points with signed coordinates, in two, three and four dimensions. Unlike Point, these can go
+// negative, so they're good for stars drifting through space and such; Point stays around for
+// indexing Grids.
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+// All of them work the same way, they just differ in the number of coordinates.
+macro_rules! signed_point {
+    ($name:ident, $dimensions:expr, $($field:ident),+) => {
+        #[derive(Copy, Clone, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+        pub struct $name {
+            $(pub $field: i64),+
+        }
+
+        impl $name {
+            pub const ORIGIN: $name = $name { $($field: 0),+ };
+
+            pub fn new($($field: i64),+) -> $name {
+                $name { $($field),+ }
+            }
+
+            pub fn manhattan(&self, other: &$name) -> i64 {
+                0 $(+ (self.$field - other.$field).abs())+
+            }
+
+            // Smallest of every coordinate; together with max_each() that makes a bounding box.
+            pub fn min_each(&self, other: &$name) -> $name {
+                $name { $($field: self.$field.min(other.$field)),+ }
+            }
+
+            pub fn max_each(&self, other: &$name) -> $name {
+                $name { $($field: self.$field.max(other.$field)),+ }
+            }
+        }
+
+        impl Add for $name {
+            type Output = $name;
+            fn add(self, other: $name) -> $name {
+                $name { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl Sub for $name {
+            type Output = $name;
+            fn sub(self, other: $name) -> $name {
+                $name { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl Neg for $name {
+            type Output = $name;
+            fn neg(self) -> $name {
+                $name { $($field: -self.$field),+ }
+            }
+        }
+
+        // Scaling, e.g. velocity * time.
+        impl Mul<i64> for $name {
+            type Output = $name;
+            fn mul(self, n: i64) -> $name {
+                $name { $($field: self.$field * n),+ }
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, other: $name) {
+                *self = *self + other;
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, other: $name) {
+                *self = *self - other;
+            }
+        }
+
+        // Same as the puzzles write them: 1,-2,3
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                let coordinates = [$(self.$field),+];
+                let coordinates: Vec<String> = coordinates.iter().map(|c| c.to_string()).collect();
+                write!(f, "{}", coordinates.join(","))
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "({})", self)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = String;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let c = parse_coordinates(s, $dimensions)?;
+                let mut c = c.into_iter();
+                Ok($name { $($field: c.next().unwrap()),+ })
+            }
+        }
+    };
+}
+
+signed_point!(Point2, 2, x, y);
+signed_point!(Point3, 3, x, y, z);
+signed_point!(Point4, 4, x, y, z, w);
+
+impl Point2 {
+    // Up, left, right and down; the same order as reading order.
+    pub fn neighbours(&self) -> [Point2; 4] {
+        [
+            Point2::new(self.x, self.y - 1),
+            Point2::new(self.x - 1, self.y),
+            Point2::new(self.x + 1, self.y),
+            Point2::new(self.x, self.y + 1),
+        ]
+    }
+}
+
+impl From<crate::Point> for Point2 {
+    fn from(p: crate::Point) -> Point2 {
+        Point2::new(p.x as i64, p.y as i64)
+    }
+}
+
+// Comma separated numbers, optionally wrapped in <>, () or []; spaces don't matter. Both
+// "<-3, 11>" and "-3,11" work.
+fn parse_coordinates(s: &str, dimensions: usize) -> Result<Vec<i64>, String> {
+    let s = s.trim();
+    let inner = match (s.chars().next(), s.chars().last()) {
+        (Some('<'), Some('>')) | (Some('('), Some(')')) | (Some('['), Some(']')) => {
+            &s[1..s.len() - 1]
+        }
+        _ => s,
+    };
+    let coordinates = inner
+        .split(',')
+        .map(|c| {
+            c.trim()
+                .parse::<i64>()
+                .map_err(|e| format!("'{}': {}", c.trim(), e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if coordinates.len() != dimensions {
+        return Err(format!(
+            "expected {} coordinates, found {}",
+            dimensions,
+            coordinates.len()
+        ));
+    }
+    Ok(coordinates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("<-3, 11>".parse(), Ok(Point2::new(-3, 11)));
+        assert_eq!(" 1,-2 ".parse(), Ok(Point2::new(1, -2)));
+        assert_eq!("(1, 2, 3)".parse(), Ok(Point3::new(1, 2, 3)));
+        assert_eq!("[0,0,-1,5]".parse(), Ok(Point4::new(0, 0, -1, 5)));
+        assert!("<1, 2>".parse::<Point3>().is_err());
+        assert!("<1, x>".parse::<Point2>().is_err());
+        assert!("<1, 2".parse::<Point2>().is_err());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let p = Point2::new(3, -4);
+        assert_eq!(p.manhattan(&Point2::ORIGIN), 7);
+        assert_eq!(p + Point2::new(1, 1), Point2::new(4, -3));
+        assert_eq!(p - p, Point2::ORIGIN);
+        assert_eq!(-p * 2, Point2::new(-6, 8));
+        let mut q = Point3::new(1, 2, 3);
+        q += Point3::new(1, 1, 1);
+        q -= Point3::new(0, 0, 5);
+        assert_eq!(q, Point3::new(2, 3, -1));
+        assert_eq!(q.to_string(), "2,3,-1");
+        assert_eq!(format!("{:?}", q), "(2,3,-1)");
+        assert_eq!(
+            Point4::new(0, 0, 0, 0).manhattan(&Point4::new(1, -1, 1, -1)),
+            4
+        );
+        assert_eq!(
+            Point2::new(0, 5).min_each(&Point2::new(2, 1)),
+            Point2::new(0, 1)
+        );
+        assert_eq!(Point2::ORIGIN.neighbours()[1], Point2::new(-1, 0));
+        assert_eq!(Point2::from(crate::Point::new(2, 3)), Point2::new(2, 3));
+    }
+}
@@ -0,0 +1,62 @@
+// A tiny thread pool: a few threads take turns picking items off a shared queue, and the results
+// come back in the order the items went in, no matter which thread finished first. Good enough
+// for solving a handful of days at once; no need for anything fancier.
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::thread;
+
+// One thread per core, unless we can't tell how many there are.
+pub fn default_threads() -> usize {
+    thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+// Calls f on every item, on (at most) the given number of threads at once.
+pub fn map<T, R, F>(items: Vec<T>, threads: usize, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let threads = threads.max(1).min(items.len());
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            let sender = sender.clone();
+            let (queue, f) = (&queue, &f);
+            scope.spawn(move || loop {
+                // Don't hold on to the lock while working on the item.
+                let next = queue.lock().unwrap().next();
+                match next {
+                    Some((i, item)) => sender.send((i, f(item))).unwrap(),
+                    None => break,
+                }
+            });
+        }
+    });
+    drop(sender);
+    let mut results: Vec<(usize, R)> = receiver.into_iter().collect();
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, r)| r).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_map() {
+        // Earlier items take longer, so they finish last; the order has to survive that.
+        let items: Vec<u64> = (0..8).collect();
+        let squares = map(items, 4, |i| {
+            thread::sleep(Duration::from_millis(8 - i));
+            i * i
+        });
+        assert_eq!(squares, vec![0, 1, 4, 9, 16, 25, 36, 49]);
+        assert_eq!(map(vec![1, 2, 3], 0, |i| i + 1), vec![2, 3, 4]);
+        assert_eq!(map(Vec::<u8>::new(), 4, |i| i), Vec::<u8>::new());
+    }
+}
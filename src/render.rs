@@ -0,0 +1,402 @@
+// Pictures of what the days are up to. Day 10 started it, by painting its stars into a PNG by
+// hand; now anything that can draw itself into a Frame (see Render) can be saved as a PNG, a
+// numbered sequence of PNGs, or an animated GIF of the whole simulation:
+//
+//   Renderer::new().scale(8).animate(&mut arena, 1000, "/tmp/arena.gif")?;
+//
+// Frames are drawn one pixel per tile (or star, or pot); the renderer blows them up when saving.
+//
+// Saving pictures needs the image crate, which takes a while to build, so everything that does
+// is behind the render feature (cargo build --features render). Frames themselves are always
+// there, and can be turned into text instead, see Frame::text.
+use crate::simulation::Simulation;
+use crate::{Grid, Point2};
+#[cfg(feature = "render")]
+use image::codecs::gif::{GifEncoder, Repeat};
+#[cfg(feature = "render")]
+use image::{Delay, ImageResult, RgbImage, RgbaImage};
+use std::collections::HashMap;
+#[cfg(feature = "render")]
+use std::fs::File;
+#[cfg(feature = "render")]
+use std::io::BufWriter;
+
+pub type Color = [u8; 3];
+
+pub const BLACK: Color = [0, 0, 0];
+pub const WHITE: Color = [255, 255, 255];
+pub const GREY: Color = [128, 128, 128];
+pub const DARK_GREY: Color = [48, 48, 48];
+pub const RED: Color = [255, 0, 0];
+pub const GREEN: Color = [0, 200, 0];
+pub const YELLOW: Color = [255, 220, 0];
+
+// Frames of points are never wider or taller than this. Points further apart get squeezed
+// together, several units to a pixel: day 10's stars start out a hundred thousand apart, and a
+// frame that big would take gigabytes.
+pub const MAX_POINTS_SIDE: usize = 1000;
+
+// Colors for maps drawn with characters; anything not listed gets the fallback color.
+#[derive(Clone, Debug)]
+pub struct Palette {
+    colors: HashMap<char, Color>,
+    fallback: Color,
+}
+
+impl Palette {
+    pub fn new(fallback: Color) -> Palette {
+        Palette {
+            colors: HashMap::new(),
+            fallback,
+        }
+    }
+
+    pub fn with(mut self, c: char, color: Color) -> Palette {
+        self.colors.insert(c, color);
+        self
+    }
+
+    pub fn color(&self, c: char) -> Color {
+        *self.colors.get(&c).unwrap_or(&self.fallback)
+    }
+}
+
+// A single picture, one pixel per thing in it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize, background: Color) -> Frame {
+        Frame {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    // A pixel for every cell of the grid.
+    pub fn from_grid<T, F: Fn(&T) -> Color>(grid: &Grid<T>, color: F) -> Frame {
+        let mut frame = Frame::new(grid.width(), grid.height(), BLACK);
+        for (p, cell) in grid.iter() {
+            frame.set(p.x, p.y, color(cell));
+        }
+        frame
+    }
+
+    // A map drawn with characters, colored by the palette.
+    pub fn from_chars(grid: &Grid<char>, palette: &Palette) -> Frame {
+        Frame::from_grid(grid, |&c| palette.color(c))
+    }
+
+    // Just big enough to fit all the points, and a margin around them, unless that's more than
+    // MAX_POINTS_SIDE pixels across or down.
+    pub fn from_points(points: &[Point2], color: Color, background: Color, margin: i64) -> Frame {
+        let bounds = |coordinate: fn(&Point2) -> i64| {
+            let min = points.iter().map(coordinate).min().unwrap_or(0);
+            let max = points.iter().map(coordinate).max().unwrap_or(0);
+            (min.saturating_sub(margin), max.saturating_add(margin))
+        };
+        let (min_x, max_x) = bounds(|p| p.x);
+        let (min_y, max_y) = bounds(|p| p.y);
+        // From one end to the other, which doesn't always fit in an i64.
+        let offset = |from: i64, to: i64| (to as i128 - from as i128) as u128;
+        let span = offset(min_x, max_x).max(offset(min_y, max_y)) + 1;
+        // Units to a pixel.
+        let squeeze = span.div_ceil(MAX_POINTS_SIDE as u128);
+        let pixels = |from: i64, to: i64| (offset(from, to) / squeeze) as usize + 1;
+        let mut frame = Frame::new(pixels(min_x, max_x), pixels(min_y, max_y), background);
+        for p in points {
+            frame.set(
+                (offset(min_x, p.x) / squeeze) as usize,
+                (offset(min_y, p.y) / squeeze) as usize,
+                color,
+            );
+        }
+        frame
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Color {
+        self.pixels[y * self.width + x]
+    }
+
+    // Anything off the frame is quietly left out of the picture.
+    pub fn set(&mut self, x: usize, y: usize, color: Color) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = color;
+        }
+    }
+
+    // The same picture on a bigger canvas, in its top left corner.
+    pub fn padded(&self, width: usize, height: usize, background: Color) -> Frame {
+        let mut frame = Frame::new(width, height, background);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                frame.set(x, y, self.get(x, y));
+            }
+        }
+        frame
+    }
+
+    // A character for every pixel, a line per row; for when there's no picture to look at.
+    pub fn text<F: Fn(Color) -> char>(&self, character: F) -> String {
+        self.pixels
+            .chunks(self.width.max(1))
+            .map(|row| row.iter().map(|&c| character(c)).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // Every pixel becomes a scale x scale square.
+    #[cfg(feature = "render")]
+    pub fn image(&self, scale: u32) -> RgbImage {
+        let scale = scale.max(1);
+        RgbImage::from_fn(
+            self.width as u32 * scale,
+            self.height as u32 * scale,
+            |x, y| image::Rgb(self.get((x / scale) as usize, (y / scale) as usize)),
+        )
+    }
+}
+
+// Anything that can draw a picture of itself.
+pub trait Render {
+    fn render(&self) -> Frame;
+}
+
+// Takes a picture of the simulation, and then another one every `every` steps, until it's done
+// or has taken `steps` steps.
+pub fn record<S: Simulation + Render>(sim: &mut S, steps: usize, every: usize) -> Vec<Frame> {
+    let every = every.max(1);
+    let mut frames = vec![sim.render()];
+    for step in 1..=steps {
+        if sim.is_done() {
+            break;
+        }
+        sim.step();
+        if step % every == 0 || sim.is_done() {
+            frames.push(sim.render());
+        }
+    }
+    frames
+}
+
+// How to turn frames into files.
+#[cfg(feature = "render")]
+#[derive(Clone, Debug)]
+pub struct Renderer {
+    scale: u32,
+    every: usize,
+    delay_ms: u32,
+    background: Color,
+}
+
+#[cfg(feature = "render")]
+impl Default for Renderer {
+    fn default() -> Renderer {
+        Renderer::new()
+    }
+}
+
+#[cfg(feature = "render")]
+impl Renderer {
+    pub fn new() -> Renderer {
+        Renderer {
+            scale: 4,
+            every: 1,
+            delay_ms: 100,
+            background: BLACK,
+        }
+    }
+
+    // How many pixels across every pixel of a frame becomes.
+    pub fn scale(mut self, scale: u32) -> Renderer {
+        self.scale = scale;
+        self
+    }
+
+    // Only every n-th step of a simulation makes it into an animation.
+    pub fn every(mut self, steps: usize) -> Renderer {
+        self.every = steps;
+        self
+    }
+
+    // How long each frame of an animation stays up.
+    pub fn delay(mut self, ms: u32) -> Renderer {
+        self.delay_ms = ms;
+        self
+    }
+
+    // What's around frames smaller than the animation they're in.
+    pub fn background(mut self, color: Color) -> Renderer {
+        self.background = color;
+        self
+    }
+
+    pub fn png(&self, frame: &Frame, path: &str) -> ImageResult<()> {
+        frame.image(self.scale).save(path)
+    }
+
+    // Saves frames as prefix0000.png, prefix0001.png and so on; returns the paths.
+    pub fn sequence(&self, frames: &[Frame], prefix: &str) -> ImageResult<Vec<String>> {
+        let mut paths = vec![];
+        for (i, frame) in frames.iter().enumerate() {
+            let path = format!("{}{:04}.png", prefix, i);
+            self.png(frame, &path)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    // An animated GIF, looping forever. Frames don't need to be the same size; smaller ones are
+    // padded to fit the biggest.
+    pub fn gif(&self, frames: &[Frame], path: &str) -> ImageResult<()> {
+        let width = frames.iter().map(|f| f.width()).max().unwrap_or(0);
+        let height = frames.iter().map(|f| f.height()).max().unwrap_or(0);
+        let mut encoder = GifEncoder::new(BufWriter::new(File::create(path)?));
+        encoder.set_repeat(Repeat::Infinite)?;
+        for frame in frames {
+            let image = frame
+                .padded(width, height, self.background)
+                .image(self.scale);
+            let rgba = RgbaImage::from_fn(image.width(), image.height(), |x, y| {
+                let [r, g, b] = image.get_pixel(x, y).0;
+                image::Rgba([r, g, b, 255])
+            });
+            encoder.encode_frame(image::Frame::from_parts(
+                rgba,
+                0,
+                0,
+                Delay::from_numer_denom_ms(self.delay_ms, 1),
+            ))?;
+        }
+        Ok(())
+    }
+
+    // The one call: runs the simulation for (at most) the given number of steps, and saves it
+    // as an animated GIF. Returns the number of frames.
+    pub fn animate<S: Simulation + Render>(
+        &self,
+        sim: &mut S,
+        steps: usize,
+        path: &str,
+    ) -> ImageResult<usize> {
+        let frames = record(sim, steps, self.every);
+        self.gif(&frames, path)?;
+        Ok(frames.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Counts up to a limit, drawing itself as a bar.
+    struct Bar(usize, usize);
+
+    impl Simulation for Bar {
+        fn step(&mut self) {
+            self.0 += 1;
+        }
+
+        fn is_done(&self) -> bool {
+            self.0 == self.1
+        }
+
+        fn state_hash(&self) -> u64 {
+            self.0 as u64
+        }
+    }
+
+    impl Render for Bar {
+        fn render(&self) -> Frame {
+            Frame::from_points(
+                &(0..=self.0 as i64)
+                    .map(|x| Point2::new(x, 0))
+                    .collect::<Vec<_>>(),
+                WHITE,
+                BLACK,
+                0,
+            )
+        }
+    }
+
+    #[test]
+    fn test_frames() {
+        let grid = Grid::from_rows(vec![vec!['#', '.'], vec!['E', 'x']]).unwrap();
+        let palette = Palette::new(RED).with('#', GREY).with('.', BLACK);
+        let frame = Frame::from_chars(&grid, &palette);
+        assert_eq!(frame.get(0, 0), GREY);
+        assert_eq!(frame.get(1, 1), RED);
+        assert_eq!(frame.text(|c| if c == BLACK { '.' } else { '#' }), "#.\n##");
+
+        let points = [Point2::new(-2, 5), Point2::new(1, 6)];
+        let frame = Frame::from_points(&points, WHITE, BLACK, 1);
+        assert_eq!((frame.width(), frame.height()), (6, 4));
+        assert_eq!(frame.get(1, 1), WHITE);
+        assert_eq!(frame.get(4, 2), WHITE);
+        assert_eq!(frame.get(0, 0), BLACK);
+        assert_eq!(frame.padded(8, 4, GREEN).get(7, 3), GREEN);
+
+        // Far apart, the points get squeezed together instead of making a huge frame.
+        let points = [Point2::new(-1_000_000, 0), Point2::new(1_000_000, 5)];
+        let frame = Frame::from_points(&points, WHITE, BLACK, 0);
+        assert_eq!((frame.width(), frame.height()), (MAX_POINTS_SIDE, 1));
+        assert_eq!(frame.get(0, 0), WHITE);
+        assert_eq!(frame.get(MAX_POINTS_SIDE - 1, 0), WHITE);
+        let points = [
+            Point2::new(i64::MIN, i64::MIN),
+            Point2::new(i64::MAX, i64::MAX),
+        ];
+        let frame = Frame::from_points(&points, WHITE, BLACK, 2);
+        assert_eq!(
+            (frame.width(), frame.height()),
+            (MAX_POINTS_SIDE, MAX_POINTS_SIDE)
+        );
+    }
+
+    #[test]
+    fn test_record() {
+        let frames = record(&mut Bar(0, 5), 100, 2);
+        // The start, steps 2 and 4, and the end.
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[3].width(), 6);
+        assert_eq!(record(&mut Bar(0, 100), 3, 1).len(), 4);
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn test_image() {
+        let grid = Grid::from_rows(vec![vec!['#', '.'], vec!['E', 'x']]).unwrap();
+        let palette = Palette::new(RED).with('#', GREY).with('.', BLACK);
+        let image = Frame::from_chars(&grid, &palette).image(3);
+        assert_eq!(image.dimensions(), (6, 6));
+        assert_eq!(image.get_pixel(5, 2).0, BLACK);
+        assert_eq!(image.get_pixel(2, 5).0, RED);
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn test_animate() {
+        let path = std::env::temp_dir().join(format!("aoc-render-{}.gif", std::process::id()));
+        let path = path.to_str().unwrap();
+        assert_eq!(
+            Renderer::new().animate(&mut Bar(0, 5), 10, path).unwrap(),
+            6
+        );
+        let gif = std::fs::read(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(&gif[..6], b"GIF89a");
+    }
+}
@@ -0,0 +1,372 @@
+// Checking a simulation round by round. The puzzle text often shows what things look like after
+// some of the rounds; files under rounds/NN/ keep those printouts, copied from the puzzle, and a
+// test compares them with what the simulation does. Going wrong at round 23 then fails right
+// there, with a diff of the round, and not just with a wrong answer at the end.
+//
+// A file is a list of rounds, each under a header like the puzzle's, with blank lines between
+// them:
+//
+//   Initially:
+//   #######
+//   #.G...#   G(200)
+//   ...
+//
+//   After 1 round:
+//   ...
+//
+//   After 23 rounds:
+//   ...
+//
+//   Done after 47 full rounds:
+//   ...
+//
+// Rounds that fit on a line can go the puzzle's way too, number first: " 0: ...#..#.#..##".
+// Only the rounds in the file get checked, so it can skip the boring ones, like the puzzle does.
+// Trailing whitespace doesn't count, as editors like to strip it. build.rs turns every file into a
+// test of its own, which starts the simulation from the example of the same name under samples/.
+//
+// For examples the puzzle doesn't show round by round, AOC_UPDATE_ROUNDS set makes the tests
+// write down every round there is, instead of checking them. That only checks the code against
+// itself, mind; whatever the puzzle shows is what should end up in the file.
+use crate::simulation::Simulation;
+use std::env;
+use std::fs;
+
+// Set this to have the files written instead of checked.
+pub const UPDATE_VAR: &str = "AOC_UPDATE_ROUNDS";
+
+// Which printout of a simulation a part of the file is about.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Round {
+    // After this many full rounds; 0 is the way it starts.
+    Full(usize),
+    // The end, when it came partway through the round after this many full ones.
+    Done(usize),
+}
+
+// Everything a simulation printed on its way.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Printouts {
+    // The way it starts, and after every full round.
+    pub rounds: Vec<String>,
+    // How it ended, if it did. A step that finishes the simulation is taken to be cut short, like
+    // day 15's last round, which ends as soon as a unit finds nobody left to fight: so its
+    // printout doesn't get a round number of its own.
+    pub end: Option<String>,
+}
+
+impl Printouts {
+    fn full_rounds(&self) -> usize {
+        self.rounds.len() - 1
+    }
+
+    fn get(&self, round: Round) -> Result<&str, String> {
+        match round {
+            Round::Full(n) => self.rounds.get(n).map(|p| p.as_str()).ok_or_else(|| {
+                format!(
+                    "round {}: it was over after {} full rounds",
+                    n,
+                    self.full_rounds()
+                )
+            }),
+            Round::Done(n) => match &self.end {
+                Some(end) if n == self.full_rounds() => Ok(end),
+                Some(_) => Err(format!(
+                    "expected it to be over after {} full rounds, but it took {}",
+                    n,
+                    self.full_rounds()
+                )),
+                None => Err(format!(
+                    "expected it to be over after {} full rounds, but it was still going after {}",
+                    n,
+                    self.full_rounds()
+                )),
+            },
+        }
+    }
+}
+
+// Printouts of the simulation: the way it starts, and after every round, until it's done or has
+// gone the given number of rounds.
+pub fn record<S: Simulation, F: Fn(&S) -> String>(
+    sim: &mut S,
+    rounds: usize,
+    print: F,
+) -> Printouts {
+    let mut printouts = Printouts {
+        rounds: vec![print(sim)],
+        end: None,
+    };
+    for _ in 0..rounds {
+        if sim.is_done() {
+            break;
+        }
+        sim.step();
+        if sim.is_done() {
+            printouts.end = Some(print(sim));
+        } else {
+            printouts.rounds.push(print(sim));
+        }
+    }
+    printouts
+}
+
+// What goes in a file; see parse().
+pub fn format(printouts: &Printouts) -> String {
+    let mut sections: Vec<String> = printouts
+        .rounds
+        .iter()
+        .enumerate()
+        .map(|(n, printout)| format!("{}\n{}\n", header(Round::Full(n)), trimmed(printout)))
+        .collect();
+    if let Some(end) = &printouts.end {
+        let done = Round::Done(printouts.full_rounds());
+        sections.push(format!("{}\n{}\n", header(done), trimmed(end)));
+    }
+    sections.join("\n")
+}
+
+fn header(round: Round) -> String {
+    match round {
+        Round::Full(0) => "Initially:".to_string(),
+        Round::Full(1) => "After 1 round:".to_string(),
+        Round::Full(n) => format!("After {} rounds:", n),
+        Round::Done(n) => format!("Done after {} full rounds:", n),
+    }
+}
+
+// The round a header is for, if it is one.
+fn parse_header(line: &str) -> Option<Round> {
+    let line = line.trim();
+    if line == "Initially:" {
+        return Some(Round::Full(0));
+    }
+    if let Some(n) = line
+        .strip_prefix("Done after ")
+        .and_then(|l| l.strip_suffix(" full rounds:"))
+    {
+        return n.parse().ok().map(Round::Done);
+    }
+    let n = line
+        .strip_prefix("After ")?
+        .strip_suffix(':')?
+        .trim_end_matches('s')
+        .strip_suffix(" round")?;
+    n.parse().ok().map(Round::Full)
+}
+
+// A round on a line of its own, like " 7: ...#..###.#...##", if that's what the line is.
+fn parse_one_liner(line: &str) -> Option<(Round, &str)> {
+    let (n, printout) = line.split_once(": ")?;
+    let n = n.trim().parse().ok()?;
+    Some((Round::Full(n), printout))
+}
+
+// Rounds in a file, and their printouts.
+pub fn parse(text: &str) -> Result<Vec<(Round, String)>, String> {
+    let mut rounds: Vec<(Round, Vec<&str>)> = vec![];
+    for (i, line) in text.lines().enumerate() {
+        let round = match (parse_header(line), parse_one_liner(line)) {
+            (Some(round), _) => {
+                rounds.push((round, vec![]));
+                round
+            }
+            (None, Some((round, printout))) => {
+                rounds.push((round, vec![printout]));
+                round
+            }
+            (None, None) => {
+                match rounds.last_mut() {
+                    Some((_, lines)) => lines.push(line),
+                    None if line.trim().is_empty() => (),
+                    None => return Err(format!("line {}: expected 'Initially:'", i + 1)),
+                }
+                continue;
+            }
+        };
+        let order = |r: &Round| match *r {
+            Round::Full(n) => (n, 0),
+            Round::Done(n) => (n, 1),
+        };
+        if rounds.len() > 1 && order(&rounds[rounds.len() - 2].0) >= order(&round) {
+            return Err(format!("line {}: {:?} is out of order", i + 1, round));
+        }
+    }
+    Ok(rounds
+        .into_iter()
+        .map(|(round, lines)| (round, trimmed(&lines.join("\n"))))
+        .collect())
+}
+
+// Rounds that aren't printed the way the file says they should be. Only the first one that's off
+// gets a diff, as the rest usually follow from it.
+pub fn compare(expected: &[(Round, String)], printouts: &Printouts) -> Result<(), String> {
+    for (round, want) in expected {
+        let got = trimmed(printouts.get(*round)?);
+        if got != *want {
+            return Err(format!(
+                "{} isn't what it should be (- expected, + got):\n{}",
+                header(*round).trim_end_matches(':'),
+                diff(want, &got)
+            ));
+        }
+    }
+    Ok(())
+}
+
+// Checks the simulation against the file at path, or writes the file, see UPDATE_VAR.
+pub fn check<S: Simulation, F: Fn(&S) -> String>(
+    sim: &mut S,
+    rounds: usize,
+    print: F,
+    path: &str,
+) -> Result<(), String> {
+    let printouts = record(sim, rounds, print);
+    if env::var_os(UPDATE_VAR).is_some() {
+        return fs::write(path, format(&printouts)).map_err(|e| format!("{}: {}", path, e));
+    }
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let expected = parse(&text).map_err(|e| format!("{}: {}", path, e))?;
+    compare(&expected, &printouts).map_err(|e| format!("{}: {}", path, e))
+}
+
+// Every line without trailing whitespace, and no empty lines at either end.
+fn trimmed(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().map(|l| l.trim_end()).collect();
+    lines.join("\n").trim_matches('\n').to_string()
+}
+
+// Line by line; both sides are printouts of the same thing, so lines don't come and go much.
+fn diff(expected: &str, got: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let got: Vec<&str> = got.lines().collect();
+    let mut lines = vec![];
+    for i in 0..expected.len().max(got.len()) {
+        match (expected.get(i), got.get(i)) {
+            (Some(e), Some(g)) if e == g => lines.push(format!("  {}", e)),
+            (e, g) => {
+                lines.extend(e.map(|e| format!("- {}", e)));
+                lines.extend(g.map(|g| format!("+ {}", g)));
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Counts up to 3, drawing a bar; the step that gets it to 3 is cut short.
+    struct Bar(usize);
+
+    impl Simulation for Bar {
+        fn step(&mut self) {
+            self.0 += 1;
+        }
+
+        fn is_done(&self) -> bool {
+            self.0 == 3
+        }
+
+        fn state_hash(&self) -> u64 {
+            self.0 as u64
+        }
+    }
+
+    fn bar(sim: &Bar) -> String {
+        "#".repeat(sim.0)
+    }
+
+    #[test]
+    fn test_record() {
+        let printouts = record(&mut Bar(0), 10, bar);
+        assert_eq!(printouts.rounds, vec!["", "#", "##"]);
+        assert_eq!(printouts.end, Some("###".to_string()));
+        let text = format(&printouts);
+        assert_eq!(
+            text,
+            "Initially:\n\n\nAfter 1 round:\n#\n\nAfter 2 rounds:\n##\n\n\
+             Done after 2 full rounds:\n###\n"
+        );
+        assert_eq!(
+            parse(&text),
+            Ok(vec![
+                (Round::Full(0), "".to_string()),
+                (Round::Full(1), "#".to_string()),
+                (Round::Full(2), "##".to_string()),
+                (Round::Done(2), "###".to_string()),
+            ])
+        );
+        let printouts = record(&mut Bar(0), 1, bar);
+        assert_eq!((printouts.rounds.len(), printouts.end), (2, None));
+    }
+
+    #[test]
+    fn test_parse() {
+        let text = "\nInitially:\nab  \ncd\n\nAfter 2 rounds:\nef\n";
+        assert_eq!(
+            parse(text),
+            Ok(vec![
+                (Round::Full(0), "ab\ncd".to_string()),
+                (Round::Full(2), "ef".to_string())
+            ])
+        );
+        assert_eq!(
+            parse(" 0: ..#.\n 1: .#..\n10: #...\n"),
+            Ok(vec![
+                (Round::Full(0), "..#.".to_string()),
+                (Round::Full(1), ".#..".to_string()),
+                (Round::Full(10), "#...".to_string())
+            ])
+        );
+        assert_eq!(
+            parse("ab\n"),
+            Err("line 1: expected 'Initially:'".to_string())
+        );
+        assert_eq!(
+            parse("After 2 rounds:\nAfter 1 round:\n"),
+            Err("line 2: Full(1) is out of order".to_string())
+        );
+        assert_eq!(
+            parse("Done after 2 full rounds:\nAfter 2 rounds:\n"),
+            Err("line 2: Full(2) is out of order".to_string())
+        );
+    }
+
+    #[test]
+    fn test_compare() {
+        let printouts = record(&mut Bar(0), 10, bar);
+        let expected = parse("After 1 round:\n#\n\nDone after 2 full rounds:\n###").unwrap();
+        assert_eq!(compare(&expected, &printouts), Ok(()));
+        assert_eq!(
+            compare(&[(Round::Full(2), "##\nbaz".to_string())], &printouts),
+            Err(
+                "After 2 rounds isn't what it should be (- expected, + got):\n  ##\n- baz"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            compare(&[(Round::Full(1), "##".to_string())], &printouts),
+            Err(
+                "After 1 round isn't what it should be (- expected, + got):\n- ##\n+ #".to_string()
+            )
+        );
+        assert_eq!(
+            compare(&[(Round::Full(5), "#####".to_string())], &printouts),
+            Err("round 5: it was over after 2 full rounds".to_string())
+        );
+        assert_eq!(
+            compare(&[(Round::Done(3), "###".to_string())], &printouts),
+            Err("expected it to be over after 3 full rounds, but it took 2".to_string())
+        );
+        let unfinished = record(&mut Bar(0), 1, bar);
+        assert_eq!(
+            compare(&[(Round::Done(1), "#".to_string())], &unfinished),
+            Err(
+                "expected it to be over after 1 full rounds, but it was still going after 1"
+                    .to_string()
+            )
+        );
+    }
+}
@@ -0,0 +1,295 @@
+// Lots of puzzles are about something that moves one step at a time: marbles getting placed,
+// stars drifting, plants growing, carts zooming around, elves and goblins fighting. Simulation is
+// what they have in common, and the drivers below know how to run any of them.
+use crate::cycles::{self, Cycle};
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+pub trait Simulation {
+    // Moves everything one step forward.
+    fn step(&mut self);
+
+    // Whether there's nothing left to simulate. Some simulations go on forever, those have to be
+    // stopped by whoever runs them.
+    fn is_done(&self) -> bool {
+        false
+    }
+
+    // Tells states apart: two states with the same hash are taken to be the same one, e.g. when
+    // looking for cycles. It's fine to leave out things that don't matter, like how many steps
+    // have been taken so far; see state_hash() below for a quick way to hash things.
+    fn state_hash(&self) -> u64;
+
+    // A copy of the current state, to look at later.
+    fn snapshot(&self) -> Self
+    where
+        Self: Sized + Clone,
+    {
+        self.clone()
+    }
+}
+
+// Hashes anything hashable; handy for implementing Simulation::state_hash.
+pub fn state_hash<T: Hash + ?Sized>(t: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    t.hash(&mut hasher);
+    hasher.finish()
+}
+
+// The simulation didn't get where it was going within its step budget.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GaveUp {
+    pub steps: usize,
+}
+
+impl fmt::Display for GaveUp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "gave up after {} steps", self.steps)
+    }
+}
+
+impl std::error::Error for GaveUp {}
+
+// How long something may go on before we give up on it: a number of steps, some time, or both.
+// Whatever loops calls spend() once for every step it takes, and bails out when that fails.
+//
+//   let mut budget = Budget::unlimited().steps(1_000_000).time(Duration::from_secs(10));
+//   loop {
+//       budget.spend()?;
+//       ...
+//   }
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Budget {
+    steps: Option<usize>,
+    time: Option<Duration>,
+    spent: usize,
+    started: Option<Instant>,
+}
+
+impl Budget {
+    // Looking at the clock takes a while, so it only happens this often.
+    const CHECK_TIME_EVERY: usize = 1024;
+
+    pub fn unlimited() -> Budget {
+        Budget::default()
+    }
+
+    pub fn steps(mut self, steps: usize) -> Budget {
+        self.steps = Some(steps);
+        self
+    }
+
+    // The clock starts ticking with the first step.
+    pub fn time(mut self, time: Duration) -> Budget {
+        self.time = Some(time);
+        self
+    }
+
+    // Steps taken so far.
+    pub fn spent(&self) -> usize {
+        self.spent
+    }
+
+    // Takes a step, unless we're out of steps or time.
+    pub fn spend(&mut self) -> Result<(), GaveUp> {
+        let started = *self.started.get_or_insert_with(Instant::now);
+        let out_of_steps = self.steps.is_some_and(|steps| self.spent >= steps);
+        let out_of_time = self.spent.is_multiple_of(Self::CHECK_TIME_EVERY)
+            && self.time.is_some_and(|time| started.elapsed() >= time);
+        if out_of_steps || out_of_time {
+            return Err(GaveUp { steps: self.spent });
+        }
+        self.spent += 1;
+        Ok(())
+    }
+}
+
+// Runs simulations, with an optional step budget and progress reports along the way:
+//
+//   Driver::new()
+//       .budget(Budget::unlimited().steps(1_000_000))
+//       .progress(100, |step, arena| debug!("{}: {:?}", step, arena))
+//       .run(&mut arena)?;
+pub struct Driver<'a, S> {
+    budget: Budget,
+    every: usize,
+    progress: Option<Progress<'a, S>>,
+}
+
+type Progress<'a, S> = Box<dyn FnMut(usize, &S) + 'a>;
+
+impl<'a, S: Simulation> Default for Driver<'a, S> {
+    fn default() -> Self {
+        Driver {
+            budget: Budget::unlimited(),
+            every: 1,
+            progress: None,
+        }
+    }
+}
+
+impl<'a, S: Simulation> Driver<'a, S> {
+    pub fn new() -> Self {
+        Driver::default()
+    }
+
+    // Gives up once the budget runs out. It's shared between all the runs of this driver.
+    pub fn budget(mut self, budget: Budget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    // Calls report with the number of steps taken so far and the simulation, after every `every`
+    // steps.
+    pub fn progress<F: FnMut(usize, &S) + 'a>(mut self, every: usize, report: F) -> Self {
+        self.every = every.max(1);
+        self.progress = Some(Box::new(report));
+        self
+    }
+
+    // Steps until stop() says so, or the simulation is done. Returns the number of steps taken.
+    pub fn run_until<F: FnMut(&S) -> bool>(
+        &mut self,
+        simulation: &mut S,
+        mut stop: F,
+    ) -> Result<usize, GaveUp> {
+        let mut steps = 0;
+        while !simulation.is_done() && !stop(simulation) {
+            self.budget.spend()?;
+            simulation.step();
+            steps += 1;
+            if let Some(progress) = &mut self.progress {
+                if steps % self.every == 0 {
+                    progress(steps, simulation);
+                }
+            }
+        }
+        Ok(steps)
+    }
+
+    // Takes n steps, unless the simulation is done sooner.
+    pub fn run_n(&mut self, simulation: &mut S, n: usize) -> Result<usize, GaveUp> {
+        // stop() gets asked once before every step, and once more at the end.
+        let mut asked = 0;
+        self.run_until(simulation, |_| {
+            asked += 1;
+            asked > n
+        })
+    }
+
+    // Steps until the simulation is done.
+    pub fn run(&mut self, simulation: &mut S) -> Result<usize, GaveUp> {
+        self.run_until(simulation, |_| false)
+    }
+}
+
+// Without a budget, there's nothing to give up on.
+pub fn run_until<S: Simulation, F: FnMut(&S) -> bool>(simulation: &mut S, stop: F) -> usize {
+    Driver::new().run_until(simulation, stop).unwrap()
+}
+
+pub fn run_n<S: Simulation>(simulation: &mut S, n: usize) -> usize {
+    Driver::new().run_n(simulation, n).unwrap()
+}
+
+pub fn run<S: Simulation>(simulation: &mut S) -> usize {
+    Driver::new().run(simulation).unwrap()
+}
+
+// Looks for a cycle in the states of a simulation, as told apart by state_hash(); gives up after
+// limit steps. See the cycles module for what to do with it.
+pub fn find_cycle<S: Simulation + Clone>(simulation: &S, limit: usize) -> Option<Cycle<S>> {
+    let step = |s: &S| {
+        let mut next = s.snapshot();
+        next.step();
+        next
+    };
+    cycles::find_cycle(simulation.snapshot(), step, S::state_hash, limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Counts up to a limit, going back to zero every 10 steps.
+    #[derive(Clone, Debug)]
+    struct Counter {
+        count: u32,
+        limit: u32,
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) {
+            self.count += 1;
+        }
+
+        fn is_done(&self) -> bool {
+            self.count >= self.limit
+        }
+
+        fn state_hash(&self) -> u64 {
+            state_hash(&(self.count % 10))
+        }
+    }
+
+    fn counter(limit: u32) -> Counter {
+        Counter { count: 0, limit }
+    }
+
+    #[test]
+    fn test_drivers() {
+        let mut c = counter(100);
+        assert_eq!(run_until(&mut c, |c| c.count == 7), 7);
+        assert_eq!(run_n(&mut c, 5), 5);
+        assert_eq!(c.count, 12);
+        assert_eq!(run_n(&mut c, 0), 0);
+        assert_eq!(run(&mut c), 88);
+        assert!(c.is_done());
+        // Done is done, no matter what.
+        assert_eq!(run_n(&mut c, 5), 0);
+        assert_eq!(c.snapshot().count, 100);
+    }
+
+    #[test]
+    fn test_budget() {
+        let mut reports = vec![];
+        let result = Driver::new()
+            .budget(Budget::unlimited().steps(25))
+            .progress(10, |step, c: &Counter| reports.push((step, c.count)))
+            .run(&mut counter(100));
+        assert_eq!(result, Err(GaveUp { steps: 25 }));
+        assert_eq!(reports, vec![(10, 10), (20, 20)]);
+        let mut driver = Driver::new().budget(Budget::unlimited().steps(25));
+        assert_eq!(driver.run(&mut counter(20)), Ok(20));
+        // The budget's shared between runs.
+        assert_eq!(driver.run(&mut counter(20)), Err(GaveUp { steps: 25 }));
+        assert_eq!(
+            Driver::new()
+                .budget(Budget::unlimited().steps(25))
+                .run(&mut counter(25)),
+            Ok(25)
+        );
+        assert_eq!(GaveUp { steps: 3 }.to_string(), "gave up after 3 steps");
+    }
+
+    #[test]
+    fn test_time_budget() {
+        let mut budget = Budget::unlimited().time(Duration::from_millis(20));
+        let started = Instant::now();
+        while budget.spend().is_ok() {}
+        assert!(started.elapsed() >= Duration::from_millis(20));
+        assert!(budget.spent() > 0);
+        assert!(budget.spent().is_multiple_of(Budget::CHECK_TIME_EVERY));
+        let mut budget = Budget::unlimited().steps(0);
+        assert_eq!(budget.spend(), Err(GaveUp { steps: 0 }));
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let cycle = find_cycle(&counter(100), 100).unwrap();
+        assert_eq!((cycle.start, cycle.period), (0, 10));
+        assert_eq!(cycle.state_at(1234).count, 4);
+    }
+}
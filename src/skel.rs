@@ -0,0 +1,62 @@
+use crate::*;
+
+pub struct Skel;
+
+pub struct Data {
+    length: usize,
+}
+
+fn parse_input(input: &str) -> Data {
+    Data {
+        length: input.len(),
+    }
+}
+
+fn part1(data: &Data) -> usize {
+    data.length
+}
+
+fn part2(data: &Data) -> usize {
+    data.length
+}
+
+impl Solution for Skel {
+    type Data = Data;
+
+    fn parse(&self, input: &str) -> Result<Self::Data, ParseError> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(&self, data: &Self::Data) -> Result<String, Error> {
+        Ok(part1(data).to_string())
+    }
+
+    fn part2(&self, data: &Self::Data) -> Result<String, Error> {
+        Ok(part2(data).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"
+Well here we are again
+It's always such a pleasure
+Remember when you tried
+to kill me twice?
+"#;
+
+    #[test]
+    fn test_part1() {
+        let lyrics = parse_input(INPUT);
+        assert_eq!(part1(&lyrics), 94);
+    }
+
+    #[test]
+    fn test_solution() {
+        let report = Skel.run(INPUT, None).unwrap();
+        assert_eq!(report.part1.unwrap(), "94");
+        assert_eq!(report.part2.unwrap(), "94");
+    }
+}
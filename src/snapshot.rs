@@ -0,0 +1,82 @@
+// Parsed puzzles, and simulations part way through, saved as JSON and loaded back. Good for
+// attaching the state where things go wrong to a bug report, or for starting a test from some
+// round of a simulation instead of from the beginning:
+//
+//   let arena: Arena = snapshot::from_json(&fs::read_to_string("arena-round-23.json")?)?;
+//
+// Needs the serde feature (cargo build --features serde); the types that can be saved (Schedule,
+// Step, Node, Mine, Arena and what's in them) only derive Serialize and Deserialize when it's on.
+use crate::{Error, ParseError};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+// Pretty printed, so that a snapshot can be read, and tweaked, by hand.
+pub fn to_json<T: Serialize>(value: &T) -> Result<String, Error> {
+    // Only maps with keys that aren't strings or numbers can't be saved; none of ours have any
+    // today, but that's no reason to panic if one turns up.
+    serde_json::to_string_pretty(value)
+        .map_err(|e| Error::Snapshot(format!("can't save as JSON: {}", e)))
+}
+
+// Anything wrong with the snapshot comes back as a ParseError, so it gets the same diagnostics
+// as a broken puzzle input.
+pub fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, ParseError> {
+    serde_json::from_str(json).map_err(|e| {
+        // serde_json tacks the position onto the message; ParseError has a place of its own for it.
+        let message = e.to_string();
+        let message = message
+            .rsplit_once(" at line ")
+            .map_or(&message[..], |(m, _)| m);
+        ParseError::new(
+            e.line().max(1),
+            e.column().max(1),
+            "",
+            &format!("a snapshot ({})", message),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day07::Step;
+    use crate::Grid;
+
+    #[test]
+    fn test_round_trip() {
+        let steps = crate::day07::parse_input(
+            "Step C must be finished before step A can begin.\n\
+             Step C must be finished before step F can begin.",
+        )
+        .unwrap();
+        let loaded: Vec<Step> = from_json(&to_json(&steps).unwrap()).unwrap();
+        assert_eq!(loaded, steps);
+
+        let broken = from_json::<Vec<Step>>("[\n  {\"name\": \"C\"}\n]").unwrap_err();
+        assert_eq!(broken.line, 2);
+        assert_eq!(
+            broken.expected,
+            "a snapshot (missing field `prerequisites`)"
+        );
+    }
+
+    // A grid edited by hand has to still be a grid.
+    #[test]
+    fn test_grid() {
+        let grid = Grid::from_rows(vec![vec!['#', '.'], vec!['.', '#']]).unwrap();
+        let loaded: Grid<char> = from_json(&to_json(&grid).unwrap()).unwrap();
+        assert_eq!(loaded, grid);
+
+        let broken = from_json::<Grid<char>>("{\"width\": 2, \"height\": 2, \"cells\": [\"#\"]}")
+            .unwrap_err();
+        assert_eq!(
+            broken.expected,
+            "a snapshot (a 2x2 grid can't have 1 cells)"
+        );
+        let huge = format!(
+            "{{\"width\": {}, \"height\": 2, \"cells\": []}}",
+            usize::MAX
+        );
+        assert!(from_json::<Grid<char>>(&huge).is_err());
+    }
+}
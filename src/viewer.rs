@@ -0,0 +1,298 @@
+// Watching a simulation in the terminal, one step at a time; forwards, backwards, or straight to
+// the interesting bits. A simulation that can describe itself (see Watch) gets recorded into a
+// list of scenes first, and the viewer then plays them back:
+//
+//   Viewer::new(viewer::record(&mut arena, 1000)).run(io::stdin().lock(), io::stdout())?;
+//
+// There's no GUI, and no raw terminal mode either: commands are typed in, followed by Enter.
+// Colors and clearing the screen need a terminal that knows ANSI escapes, which they all do.
+use crate::simulation::Simulation;
+use std::io::{self, BufRead, Write};
+use std::thread;
+use std::time::Duration;
+
+// Something that happened, like a cart crashing or a goblin falling; maybe at some place on the
+// picture, which then gets highlighted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Event {
+    pub at: Option<(usize, usize)>,
+    pub text: String,
+}
+
+// What the simulation looks like at some step.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Scene {
+    pub step: usize,
+    // The picture itself, a line of text per row.
+    pub picture: Vec<String>,
+    // Anything else worth knowing, shown under the picture.
+    pub notes: Vec<String>,
+    // What happened on this step.
+    pub events: Vec<Event>,
+}
+
+// A simulation that can describe itself. Events are everything that's happened so far (every
+// crash, every fallen unit); record() works out which of them are new.
+pub trait Watch {
+    fn scene(&self) -> Scene;
+}
+
+// Scenes of the simulation, from the start until it's done, or for at most the given number of
+// steps.
+pub fn record<S: Simulation + Watch>(sim: &mut S, steps: usize) -> Vec<Scene> {
+    let mut scenes = vec![sim.scene()];
+    let mut seen = scenes[0].events.clone();
+    for step in 1..=steps {
+        if sim.is_done() {
+            break;
+        }
+        sim.step();
+        let mut scene = sim.scene();
+        scene.step = step;
+        scene.events.retain(|e| !seen.contains(e));
+        seen.extend(scene.events.iter().cloned());
+        scenes.push(scene);
+    }
+    scenes
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Command {
+    Forward(usize),
+    Back(usize),
+    Go(usize),
+    NextEvent,
+    PreviousEvent,
+    // Plays the scenes, pausing at the next event; the number is milliseconds per scene.
+    Play(u64),
+    Help,
+    Quit,
+}
+
+const HELP: &str =
+    "Enter or n [k]: k steps forward     b [k]: k steps back     g <step>: go to step
+e: next event     E: previous event     p [ms]: play until the next event     q: quit";
+
+const PLAY_DELAY_MS: u64 = 100;
+
+impl Command {
+    fn parse(line: &str) -> Result<Command, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let count = |w: Option<&&str>, default| match w {
+            None => Ok(default),
+            Some(w) => w.parse().map_err(|_| format!("'{}' is not a number", w)),
+        };
+        match words.first().cloned().unwrap_or("n") {
+            "n" => Ok(Command::Forward(count(words.get(1), 1)?)),
+            "b" => Ok(Command::Back(count(words.get(1), 1)?)),
+            "g" if words.len() == 2 => Ok(Command::Go(count(words.get(1), 0)?)),
+            "g" => Err("go where? g <step>".to_string()),
+            "e" => Ok(Command::NextEvent),
+            "E" => Ok(Command::PreviousEvent),
+            "p" => Ok(Command::Play(
+                count(words.get(1), PLAY_DELAY_MS as usize)? as u64
+            )),
+            "h" | "?" => Ok(Command::Help),
+            "q" => Ok(Command::Quit),
+            w => Err(format!("what's '{}'? h for help", w)),
+        }
+    }
+}
+
+pub struct Viewer {
+    scenes: Vec<Scene>,
+    // Index of the scene on screen.
+    at: usize,
+    // Whatever needs saying under the scene: help, or what went wrong with the last command.
+    message: String,
+}
+
+impl Viewer {
+    pub fn new(scenes: Vec<Scene>) -> Viewer {
+        Viewer {
+            scenes,
+            at: 0,
+            message: HELP.to_string(),
+        }
+    }
+
+    // Reads commands until q, or until there are no more.
+    pub fn run<R: BufRead, W: Write>(&mut self, input: R, mut output: W) -> io::Result<()> {
+        if self.scenes.is_empty() {
+            return writeln!(output, "Nothing to watch.");
+        }
+        self.show(&mut output)?;
+        for line in input.lines() {
+            match Command::parse(&line?) {
+                Ok(Command::Quit) => break,
+                Ok(Command::Play(ms)) => self.play(&mut output, ms)?,
+                Ok(command) => self.apply(command),
+                Err(e) => self.message = e,
+            }
+            self.show(&mut output)?;
+        }
+        Ok(())
+    }
+
+    fn apply(&mut self, command: Command) {
+        let last = self.scenes.len() - 1;
+        let has_events = |s: &Scene| !s.events.is_empty();
+        self.message.clear();
+        match command {
+            Command::Forward(k) => self.at = (self.at + k).min(last),
+            Command::Back(k) => self.at = self.at.saturating_sub(k),
+            // The first scene for the step, or the closest one before it.
+            Command::Go(step) => {
+                self.at = self
+                    .scenes
+                    .iter()
+                    .rposition(|s| s.step <= step)
+                    .unwrap_or(0)
+            }
+            Command::NextEvent => match self.scenes[self.at + 1..].iter().position(has_events) {
+                Some(i) => self.at += i + 1,
+                None => self.message = "Nothing else happens.".to_string(),
+            },
+            Command::PreviousEvent => match self.scenes[..self.at].iter().rposition(has_events) {
+                Some(i) => self.at = i,
+                None => self.message = "Nothing happened before.".to_string(),
+            },
+            Command::Help => self.message = HELP.to_string(),
+            Command::Play(_) | Command::Quit => (),
+        }
+    }
+
+    fn play<W: Write>(&mut self, output: &mut W, ms: u64) -> io::Result<()> {
+        self.message.clear();
+        while self.at + 1 < self.scenes.len() {
+            self.at += 1;
+            if !self.scenes[self.at].events.is_empty() {
+                self.message = "Paused.".to_string();
+                break;
+            }
+            self.show(output)?;
+            thread::sleep(Duration::from_millis(ms));
+        }
+        Ok(())
+    }
+
+    fn show<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        let scene = &self.scenes[self.at];
+        // Clear the screen, and start at the top.
+        write!(output, "\x1b[2J\x1b[H")?;
+        writeln!(
+            output,
+            "Step {} (scene {} of {})",
+            scene.step,
+            self.at + 1,
+            self.scenes.len()
+        )?;
+        for (y, row) in scene.picture.iter().enumerate() {
+            writeln!(output, "{}", highlight(row, y, &scene.events))?;
+        }
+        for note in &scene.notes {
+            writeln!(output, "{}", note)?;
+        }
+        for event in &scene.events {
+            writeln!(output, "\x1b[1;31m* {}\x1b[0m", event.text)?;
+        }
+        writeln!(output, "{}", self.message)?;
+        write!(output, "> ")?;
+        output.flush()
+    }
+}
+
+// The row, with the places where something happened in bold red.
+fn highlight(row: &str, y: usize, events: &[Event]) -> String {
+    row.chars()
+        .enumerate()
+        .map(|(x, c)| {
+            if events.iter().any(|e| e.at == Some((x, y))) {
+                format!("\x1b[1;31m{}\x1b[0m", c)
+            } else {
+                c.to_string()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Counts up; something happens on every multiple of 3.
+    struct Counter(usize);
+
+    impl Simulation for Counter {
+        fn step(&mut self) {
+            self.0 += 1;
+        }
+
+        fn is_done(&self) -> bool {
+            self.0 == 10
+        }
+
+        fn state_hash(&self) -> u64 {
+            self.0 as u64
+        }
+    }
+
+    impl Watch for Counter {
+        fn scene(&self) -> Scene {
+            Scene {
+                picture: vec![format!("{:>10}", "#".repeat(self.0))],
+                events: (1..=self.0)
+                    .filter(|i| i % 3 == 0)
+                    .map(|i| Event {
+                        at: Some((10 - i, 0)),
+                        text: format!("{} reached", i),
+                    })
+                    .collect(),
+                ..Scene::default()
+            }
+        }
+    }
+
+    #[test]
+    fn test_record() {
+        let scenes = record(&mut Counter(0), 100);
+        assert_eq!(scenes.len(), 11);
+        assert_eq!(scenes[4].step, 4);
+        assert!(scenes[4].events.is_empty());
+        assert_eq!(scenes[6].events.len(), 1);
+        assert_eq!(scenes[6].events[0].text, "6 reached");
+        assert_eq!(record(&mut Counter(0), 3).len(), 4);
+    }
+
+    #[test]
+    fn test_commands() {
+        let mut viewer = Viewer::new(record(&mut Counter(0), 100));
+        let mut run = |commands: &str| {
+            let mut output = vec![];
+            viewer.run(commands.as_bytes(), &mut output).unwrap();
+            viewer.at
+        };
+        assert_eq!(run("\n\nn 2\n"), 4);
+        assert_eq!(run("b\n"), 3);
+        assert_eq!(run("e\n"), 6);
+        assert_eq!(run("E\nE\n"), 3);
+        assert_eq!(run("g 8\n"), 8);
+        assert_eq!(run("n 100\nq\nb\n"), 10);
+        assert_eq!(run("p 0\n"), 10);
+        assert_eq!(run("g 0\np 0\n"), 3);
+        assert_eq!(run("whatever\n"), 3);
+        assert_eq!(viewer.message, "what's 'whatever'? h for help");
+        assert_eq!(Command::parse("g"), Err("go where? g <step>".to_string()));
+        assert_eq!(Command::parse(""), Ok(Command::Forward(1)));
+    }
+
+    #[test]
+    fn test_highlight() {
+        let events = vec![Event {
+            at: Some((1, 2)),
+            text: "boom".to_string(),
+        }];
+        assert_eq!(highlight("abc", 2, &events), "a\x1b[1;31mb\x1b[0mc");
+        assert_eq!(highlight("abc", 1, &events), "abc");
+    }
+}